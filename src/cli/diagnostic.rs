@@ -1,6 +1,6 @@
 use crate::cli::{Cli, CommandHandler};
 use crate::config::Config;
-use crate::git;
+use crate::{git, tracker};
 
 pub struct Diagnostic {}
 
@@ -11,16 +11,26 @@ impl Diagnostic {
 }
 
 impl CommandHandler for Diagnostic {
-    fn execute(&self, _cli: &Cli) -> anyhow::Result<()> {
-        git::Git::validate_git_installed()?;
+    fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        let git = git::open(cli.repo.as_deref())?;
+        git.validate_git_installed()?;
         println!("Git is installed.");
-        git::Git::validate_git_repo()?;
+        git.validate_git_repo()?;
         println!("Git repository is valid.");
-        let _ = Config::read_config_file_from_home_dir()?;
+        let repo_root = git.get_toplevel()?;
+        let config = Config::read_config_file(Some(repo_root.as_str()))?;
         println!("Config file is valid.");
 
-        // TODO: diagnostic for project management tool
-        println!("Project management tool (skipped).");
+        match &config.project_management {
+            Some(project_management) => {
+                tracker::open(project_management, false)?.validate_connection()?;
+                println!(
+                    "Project management tool ({:?}) is valid.",
+                    project_management.provider
+                );
+            }
+            None => println!("Project management tool (not configured)."),
+        }
 
         println!("Diagnostic done.");
         Ok(())
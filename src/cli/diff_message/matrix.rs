@@ -0,0 +1,324 @@
+use crate::cli::diff_message::{
+    compile_ticket_id_regex, extract_ticket_number, fetch_tickets, load_commits_as_map, output,
+    prepare_revisions, print_repository_header, project_management, read_config, read_ignore_rules,
+    reject_relative_tickets, with_repository,
+};
+use crate::cli::{Cli, DiffMessageParams, MatrixRows, OutputFormat, TicketField};
+use crate::error::GinspError;
+use crate::ignore::IgnoreRules;
+use crate::{git, tracker};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Presence of each commit (or ticket) on each of the compared revisions.
+/// Only rows that are missing on at least one revision are kept.
+#[derive(Serialize)]
+pub struct Matrix {
+    pub repository: String,
+    pub revisions: Vec<git::Revision>,
+    pub rows: Vec<MatrixRow>,
+}
+
+#[derive(Serialize)]
+pub struct MatrixRow {
+    /// Commit message, or ticket id with `--rows ticket`.
+    pub key: String,
+    /// Short hash of the first commit found for the row.
+    pub short_sha: String,
+    pub ticket: Option<String>,
+    pub status: Option<String>,
+    /// Whether the row is on each revision, in the order of `Matrix::revisions`.
+    pub present: Vec<bool>,
+}
+
+/// Compare more than two revisions at once.
+pub fn execute(cli: &Cli, options: &DiffMessageParams) -> anyhow::Result<()> {
+    if options.pick_contains.is_some() {
+        return Err(GinspError::Cli(
+            "Cherry-pick option requires exactly 2 revisions to compare.".to_string(),
+        )
+        .into());
+    }
+
+    if options.fuzzy.is_some() {
+        return Err(GinspError::Cli(
+            "Fuzzy option requires exactly 2 revisions to compare.".to_string(),
+        )
+        .into());
+    }
+
+    if options.detect_squash {
+        return Err(GinspError::Cli(
+            "Detect squash option requires exactly 2 revisions to compare.".to_string(),
+        )
+        .into());
+    }
+
+    if options.stat || options.name_only {
+        return Err(GinspError::Cli(
+            "Stat and name-only options require exactly 2 revisions to compare.".to_string(),
+        )
+        .into());
+    }
+
+    if options.ticket_fields != [TicketField::Status] {
+        return Err(GinspError::Cli(
+            "Ticket fields option requires exactly 2 revisions to compare.".to_string(),
+        )
+        .into());
+    }
+
+    // ticket ids are needed to group rows by ticket and to fetch statuses
+    let profile = if options.matrix_rows == MatrixRows::Ticket || options.is_fetch_ticket_status {
+        Some(read_config(cli)?)
+    } else {
+        None
+    };
+    let ticket_id_regex = match &profile {
+        Some(profile) => Some(compile_ticket_id_regex(
+            &project_management(profile)?.ticket_id_regex,
+        )?),
+        None => None,
+    };
+
+    let is_workspace = cli.workspace.is_some();
+
+    let mut matrices = vec![];
+    for repository in cli.repositories()? {
+        let (revisions, rows) = git::open(repository.path.as_deref())
+            .map_err(anyhow::Error::from)
+            .and_then(|git| build_rows(git.as_ref(), options, ticket_id_regex.as_ref()))
+            .map_err(|err| with_repository(err, &repository.name, is_workspace))?;
+
+        matrices.push(Matrix {
+            repository: repository.name,
+            revisions,
+            rows,
+        });
+    }
+
+    if options.is_fetch_ticket_status {
+        if let Some(profile) = &profile {
+            let tracker = tracker::open_cached(
+                project_management(profile)?,
+                options.ticket_cache.cache_mode(),
+                options.verbose,
+            )?;
+            if is_workspace {
+                for matrix in matrices.iter() {
+                    reject_relative_tickets(
+                        tracker.as_ref(),
+                        &matrix.repository,
+                        matrix.rows.iter().filter_map(|row| row.ticket.as_ref()),
+                    )?;
+                }
+            }
+
+            let mut tickets = HashMap::new();
+            let rows = matrices
+                .iter_mut()
+                .flat_map(|matrix| matrix.rows.iter_mut())
+                .collect::<Vec<_>>();
+
+            fetch_tickets(
+                rows.iter().filter_map(|row| row.ticket.as_ref()),
+                tracker.as_ref(),
+                &mut tickets,
+                options.verbose,
+            );
+
+            for row in rows {
+                row.status = row
+                    .ticket
+                    .as_ref()
+                    .and_then(|ticket| tickets.get(ticket).cloned().flatten())
+                    .map(|ticket| ticket.status_label());
+            }
+        }
+    }
+
+    match options.format {
+        OutputFormat::Table => {
+            for matrix in matrices {
+                if is_workspace {
+                    print_repository_header(&matrix.repository);
+                }
+                print_matrix(matrix);
+            }
+            println!();
+        }
+        OutputFormat::Json => output::print_json(&matrices)?,
+        OutputFormat::Csv => print_csv(&matrices)?,
+    }
+
+    Ok(())
+}
+
+fn build_rows(
+    git: &dyn git::GitBackend,
+    options: &DiffMessageParams,
+    ticket_id_regex: Option<&Regex>,
+) -> anyhow::Result<(Vec<git::Revision>, Vec<MatrixRow>)> {
+    git.validate_git_installed()?;
+    git.validate_git_repo()?;
+
+    let revisions = prepare_revisions(git, options)?;
+
+    let log_options = options.filters.log_options();
+
+    let ignore_rules = if options.filters.no_ignore {
+        IgnoreRules::default()
+    } else {
+        read_ignore_rules(git)?
+    };
+
+    let revisions_len = revisions.len();
+    let mut rows = indexmap::IndexMap::<String, MatrixRow>::new();
+
+    for (index, revision) in revisions.iter().enumerate() {
+        let commits = load_commits_as_map(git, &revision.name, &log_options)?;
+
+        for (message, commit) in commits.iter() {
+            if ignore_rules.is_ignored(commit) {
+                continue;
+            }
+
+            let ticket = ticket_id_regex
+                .and_then(|ticket_id_regex| extract_ticket_number(message, ticket_id_regex));
+            let key = match (options.matrix_rows, &ticket) {
+                (MatrixRows::Ticket, Some(ticket)) => ticket.to_string(),
+                _ => message.to_string(),
+            };
+
+            let row = rows.entry(key.clone()).or_insert_with(|| MatrixRow {
+                key,
+                short_sha: commit.short_sha.to_string(),
+                ticket,
+                status: None,
+                present: vec![false; revisions_len],
+            });
+            row.present[index] = true;
+        }
+    }
+
+    let rows = rows
+        .into_values()
+        .filter(|row| !row.present.iter().all(|present| *present))
+        .collect();
+
+    Ok((revisions, rows))
+}
+
+/// Print the matrix as table like this
+/// ```
+/// Commit messages by revision:
+/// ------------------------
+///   master  release-1  v1.2.0
+///   branch  branch     tag
+///   ✓       -          ✓       eec4f1c [ABC-10370] message
+///   ✓       -          -       54912eb [ABC-10365] message
+/// ```
+fn print_matrix(matrix: Matrix) {
+    println!("\nCommit messages by revision:");
+    println!("------------------------");
+
+    let widths = matrix
+        .revisions
+        .iter()
+        .map(|revision| {
+            revision
+                .name
+                .chars()
+                .count()
+                .max(revision.kind.to_string().len())
+        })
+        .collect::<Vec<_>>();
+    let max_status_len = matrix
+        .rows
+        .iter()
+        .map(|row| row.status.as_ref().map_or(0, |s| s.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    let names = matrix
+        .revisions
+        .iter()
+        .zip(widths.iter())
+        .map(|(revision, width)| format!("{:width$}", revision.name, width = width))
+        .collect::<Vec<_>>();
+    let kinds = matrix
+        .revisions
+        .iter()
+        .zip(widths.iter())
+        .map(|(revision, width)| format!("{:width$}", revision.kind.to_string(), width = width))
+        .collect::<Vec<_>>();
+    println!("  {}", names.join("  ").trim_end());
+    println!("  {}", kinds.join("  ").trim_end());
+
+    for row in matrix.rows {
+        let mut string_vec = row
+            .present
+            .iter()
+            .zip(widths.iter())
+            .map(|(present, width)| {
+                format!("{:width$}", if *present { "✓" } else { "-" }, width = width)
+            })
+            .collect::<Vec<_>>();
+
+        if max_status_len > 0 {
+            string_vec.push(format!(
+                "{:width$}",
+                row.status.unwrap_or_default(),
+                width = max_status_len
+            ));
+        }
+
+        string_vec.push(row.short_sha);
+        string_vec.push(row.key);
+
+        println!("  {}", string_vec.join("  "));
+    }
+}
+
+/// Print the matrices as CSV, one line per row with one column per revision.
+fn print_csv(matrices: &[Matrix]) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+
+    let revisions = matrices
+        .first()
+        .map(|matrix| {
+            matrix
+                .revisions
+                .iter()
+                .map(|revision| revision.name.to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let mut header = vec!["repository", "key", "short_sha", "ticket", "status"];
+    header.extend(revisions.iter().map(String::as_str));
+    writer.write_record(header)?;
+
+    for matrix in matrices.iter() {
+        for row in matrix.rows.iter() {
+            let mut record = vec![
+                matrix.repository.to_string(),
+                row.key.to_string(),
+                row.short_sha.to_string(),
+                row.ticket.clone().unwrap_or_default(),
+                row.status.clone().unwrap_or_default(),
+            ];
+            record.extend(row.present.iter().map(|present| {
+                if *present {
+                    "present".to_string()
+                } else {
+                    "missing".to_string()
+                }
+            }));
+            writer.write_record(record)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,1178 @@
+mod matrix;
+mod output;
+mod squash;
+
+use crate::cli::{Cli, CommandHandler, OutputFormat, TicketField};
+use crate::config::{Config, ProjectManagement};
+use crate::error::{ConfigErrorKind, GinspError};
+use crate::fuzzy::NormalizedSubject;
+use crate::ignore::IgnoreRules;
+use crate::tracker::IssueTracker;
+use crate::{cli, git, tracker};
+use indexmap::indexmap;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+pub struct DiffMessage {}
+
+#[derive(Clone, Serialize)]
+pub struct CommitInfo {
+    #[serde(flatten)]
+    pub commit: git::Commit,
+    pub status: Option<String>,
+    pub is_picked: bool,
+    /// Short hash of the commit on the other side that squashes this one, with `--detect-squash`.
+    pub squashed_into: Option<String>,
+    /// Files changed by the commit, with `--stat` or `--name-only`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<git::FileStat>>,
+    /// Ticket of the commit message, with `-t`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<tracker::Ticket>,
+}
+
+impl From<&git::Commit> for CommitInfo {
+    fn from(commit: &git::Commit) -> Self {
+        CommitInfo {
+            commit: commit.clone(),
+            status: None,
+            is_picked: false,
+            squashed_into: None,
+            files: None,
+            ticket: None,
+        }
+    }
+}
+
+impl DiffMessage {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// A commit and a later commit on the same branch that reverts it.
+#[derive(Serialize)]
+pub struct RevertPair {
+    pub commit: CommitInfo,
+    pub revert: CommitInfo,
+}
+
+/// Commits of one side of the comparison that are not on the other side.
+#[derive(Serialize)]
+pub struct SideReport {
+    pub revision: git::Revision,
+    pub unique: Vec<CommitInfo>,
+    /// Number of commits hidden by the ignore rules.
+    pub ignored: usize,
+    /// Commits cancelled by a revert, left out of `unique` and of cherry-picks.
+    pub reverted: Vec<RevertPair>,
+}
+
+/// A commit on each side whose messages are similar but not equal,
+/// probably the same change reworded when it was ported.
+#[derive(Serialize)]
+pub struct FuzzyMatch {
+    pub source: CommitInfo,
+    pub target: CommitInfo,
+    pub similarity: f64,
+}
+
+/// Commits unique on each side of the comparison in one repository.
+#[derive(Serialize)]
+pub struct BranchDiff {
+    pub source: SideReport,
+    pub target: SideReport,
+    /// Matches found with `--fuzzy`, left out of both sides and of cherry-picks.
+    pub fuzzy_matches: Vec<FuzzyMatch>,
+}
+
+#[derive(Serialize)]
+pub struct RepositoryReport {
+    #[serde(rename = "repository")]
+    pub name: String,
+    #[serde(flatten)]
+    pub diff: BranchDiff,
+}
+
+impl CommandHandler for DiffMessage {
+    fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        // Get and validate command line options
+        let options = match cli.subcommand {
+            cli::SubCommand::DiffMessage(ref diff_cmd) => diff_cmd,
+            _ => return Err(GinspError::Cli("Invalid subcommand".to_string()).into()),
+        };
+
+        // Get and validate revisions
+        if options.revisions.len() < 2 {
+            let err_msg = "Provide at least 2 revisions to compare".to_string();
+            return Err(GinspError::Cli(err_msg).into());
+        }
+
+        // with `--remote` the revisions are branch names on the remote
+        match &options.remote {
+            Some(remote) => {
+                git::validate_ref_names(&[remote.to_string()])?;
+                git::validate_ref_names(&options.revisions)?;
+            }
+            None => git::validate_revisions(&options.revisions)?,
+        }
+
+        if options.revisions.len() > 2 {
+            return matrix::execute(cli, options);
+        }
+
+        let is_workspace = cli.workspace.is_some();
+
+        // a pick failing in one repository would leave the others half picked
+        if is_workspace && options.pick_contains.is_some() {
+            return Err(GinspError::Cli(
+                "Cherry-pick option works on a single repository, use -C instead of --workspace."
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let mut reports = vec![];
+        for repository in cli.repositories()? {
+            let diff = git::open(repository.path.as_deref())
+                .map_err(anyhow::Error::from)
+                .and_then(|git| diff_repository(git.as_ref(), options))
+                .map_err(|err| with_repository(err, &repository.name, is_workspace))?;
+
+            reports.push(RepositoryReport {
+                name: repository.name,
+                diff,
+            });
+        }
+
+        if options.is_fetch_ticket_status {
+            let profile = read_config(cli)?;
+            let project_management = project_management(&profile)?;
+            let tracker = tracker::open_cached(
+                project_management,
+                options.ticket_cache.cache_mode(),
+                options.verbose,
+            )?;
+            let ticket_id_regex = compile_ticket_id_regex(&project_management.ticket_id_regex)?;
+
+            if is_workspace {
+                for report in reports.iter() {
+                    let ticket_numbers = [&report.diff.source.unique, &report.diff.target.unique]
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|commit| {
+                            extract_ticket_number(&commit.commit.subject, &ticket_id_regex)
+                        })
+                        .collect::<Vec<_>>();
+                    reject_relative_tickets(tracker.as_ref(), &report.name, &ticket_numbers)?;
+                }
+            }
+
+            // ticket statuses are shared across repositories so that
+            // each ticket is fetched only once
+            let mut tickets = HashMap::new();
+            for report in reports.iter_mut() {
+                map_ticket_status(
+                    &mut report.diff.source.unique,
+                    tracker.as_ref(),
+                    &ticket_id_regex,
+                    &mut tickets,
+                    options.verbose,
+                );
+                map_ticket_status(
+                    &mut report.diff.target.unique,
+                    tracker.as_ref(),
+                    &ticket_id_regex,
+                    &mut tickets,
+                    options.verbose,
+                );
+                select_ticket_fields(&mut report.diff.source.unique, &options.ticket_fields);
+                select_ticket_fields(&mut report.diff.target.unique, &options.ticket_fields);
+            }
+        }
+
+        match options.format {
+            OutputFormat::Table => {
+                for report in reports {
+                    if is_workspace {
+                        print_repository_header(&report.name);
+                    }
+                    let diff = report.diff;
+                    print_result(diff.source, &options.ticket_fields);
+                    print_result(diff.target, &options.ticket_fields);
+                    print_fuzzy_matches(diff.fuzzy_matches);
+                }
+                println!();
+            }
+            OutputFormat::Json => output::print_json(&reports)?,
+            OutputFormat::Csv => output::print_csv(&reports, &options.ticket_fields)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Compare the two revisions in one repository and cherry-pick
+/// the matching commits when `-c` is given.
+pub(crate) fn diff_repository(
+    git: &dyn git::GitBackend,
+    options: &cli::DiffMessageParams,
+) -> anyhow::Result<BranchDiff> {
+    // validate git is installed and the current directory is a git repository
+    git.validate_git_installed()?;
+    git.validate_git_repo()?;
+
+    let [source, target]: [git::Revision; 2] = prepare_revisions(git, options)?
+        .try_into()
+        .map_err(|_| GinspError::Cli("Provide 2 revisions to compare".to_string()))?;
+
+    let is_cherry_pick = options.pick_contains.is_some();
+
+    if is_cherry_pick {
+        check_pick_target(git, &target)?;
+    }
+
+    let cherry_pick_messages = options
+        .pick_contains
+        .as_ref()
+        .map(|s| s.split(',').collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    if options.verbose {
+        if let Ok(merge_base) = git.merge_base(&source.sha, &target.sha) {
+            println!(
+                "Merge base of {} and {}: {}",
+                source.name, target.name, merge_base
+            );
+        }
+    }
+
+    // filters apply to both sides so that only the relevant slice is compared
+    let log_options = options.filters.log_options();
+
+    let source_map = load_commits_as_map(git, &source.name, &log_options)?;
+    let target_map = load_commits_as_map(git, &target.name, &log_options)?;
+
+    let ignore_rules = if options.filters.no_ignore {
+        IgnoreRules::default()
+    } else {
+        read_ignore_rules(git)?
+    };
+
+    // ignored commits are left out of the comparison, only those that would
+    // have been reported as unique are counted as hidden
+    let (filtered_source_map, ignored_in_source) =
+        remove_ignored(&source_map, &target_map, &ignore_rules);
+    let (filtered_target_map, ignored_in_target) =
+        remove_ignored(&target_map, &source_map, &ignore_rules);
+
+    let unique_to_source = unique_by_message(&filtered_source_map, &filtered_target_map);
+    let unique_to_target = unique_by_message(&filtered_target_map, &filtered_source_map);
+
+    let unique_to_source = unique_to_source
+        .into_iter()
+        .map(CommitInfo::from)
+        .collect::<Vec<_>>();
+
+    // convert unique_to_target to Vec<CommitInfo>
+    let unique_to_target = unique_to_target
+        .into_iter()
+        .map(CommitInfo::from)
+        .collect::<Vec<_>>();
+
+    // a commit and its revert cancel each other out, so they are reported
+    // separately and never cherry-picked
+    let (unique_to_source, reverted_in_source) = if options.keep_reverts {
+        (unique_to_source, vec![])
+    } else {
+        split_revert_pairs(unique_to_source)
+    };
+    let (unique_to_target, reverted_in_target) = if options.keep_reverts {
+        (unique_to_target, vec![])
+    } else {
+        split_revert_pairs(unique_to_target)
+    };
+
+    let ticket_id_regex = if options.fuzzy.is_some() || options.detect_squash {
+        read_ticket_id_regex(git)?
+    } else {
+        None
+    };
+
+    // reworded commits are only probably the same, so they are reported
+    // for a human to confirm and never cherry-picked
+    let (mut unique_to_source, mut unique_to_target, fuzzy_matches) = match options.fuzzy {
+        Some(threshold) => split_fuzzy_matches(
+            unique_to_source,
+            unique_to_target,
+            threshold,
+            ticket_id_regex.as_ref(),
+        ),
+        None => (unique_to_source, unique_to_target, vec![]),
+    };
+
+    // source commits squashed into a single target commit are already ported
+    if options.detect_squash {
+        squash::mark_squashed(
+            git,
+            &mut unique_to_source,
+            &unique_to_target,
+            ticket_id_regex.as_ref(),
+        )?;
+    }
+
+    if is_cherry_pick {
+        // commits are picked oldest first
+        let commits = unique_to_source.iter_mut().rev().filter(|commit| {
+            commit.squashed_into.is_none()
+                && cherry_pick_messages
+                    .iter()
+                    .any(|message| commit.commit.subject.contains(message))
+        });
+        cherry_pick_commits(git, commits, options.verbose)?;
+    }
+
+    if options.stat || options.name_only {
+        for commit in unique_to_source
+            .iter_mut()
+            .chain(unique_to_target.iter_mut())
+        {
+            load_files(git, commit, options.name_only)?;
+        }
+    }
+
+    Ok(BranchDiff {
+        source: SideReport {
+            revision: source,
+            unique: unique_to_source,
+            ignored: ignored_in_source,
+            reverted: reverted_in_source,
+        },
+        target: SideReport {
+            revision: target,
+            unique: unique_to_target,
+            ignored: ignored_in_target,
+            reverted: reverted_in_target,
+        },
+        fuzzy_matches,
+    })
+}
+
+/// Only the target of a cherry-pick has to be a local branch,
+/// and it has to be the current branch.
+pub(crate) fn check_pick_target(
+    git: &dyn git::GitBackend,
+    target: &git::Revision,
+) -> anyhow::Result<()> {
+    let target_branch = target.local_branch().ok_or(GinspError::Cli(format!(
+        "Cherry-pick option requires the target '{}' to be a local branch, not a {}.",
+        target.name, target.kind
+    )))?;
+
+    let current_branch = git.get_current_branch()?;
+    if current_branch != target_branch {
+        return Err(GinspError::Cli(format!(
+            "Checkout to the target branch '{}' to use cherry-pick option.",
+            target_branch
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Cherry-pick the commits in the given order and mark them as picked.
+/// When one fails, the cherry-pick is aborted and the current branch is reset
+/// to the commit it was on before the first pick.
+pub(crate) fn cherry_pick_commits<'a>(
+    git: &dyn git::GitBackend,
+    commits: impl IntoIterator<Item = &'a mut CommitInfo>,
+    verbose: bool,
+) -> anyhow::Result<()> {
+    let mut commits = commits.into_iter().peekable();
+    if commits.peek().is_none() {
+        return Ok(());
+    }
+
+    let last_commit_hash = git.rev_parse("HEAD")?;
+
+    for commit in commits {
+        let git::Commit {
+            sha,
+            short_sha,
+            subject,
+            ..
+        } = &commit.commit;
+
+        if verbose {
+            println!("Doing cherry-pick {} {}", short_sha, subject);
+        }
+
+        if git.cherry_pick(sha).is_ok() {
+            commit.is_picked = true;
+            continue;
+        }
+
+        eprintln!(
+            "Fail to cherry-pick commit. Resetting current branch to the last commit hash {}...",
+            last_commit_hash
+        );
+
+        eprintln!("Aborting cherry-pick...");
+        git.cherry_pick_abort()
+            .map(git::print_stderr)
+            .map_err(|err| GinspError::Git(format!("Fail to abort cherry-pick. Error: {}", err)))?;
+
+        eprintln!(
+            "Resetting to commit hash {} (before doing cherry-pick)...",
+            last_commit_hash
+        );
+        git.reset_hard(&last_commit_hash)
+            .map(git::print_stderr)
+            .map_err(|err| {
+                GinspError::Git(format!(
+                    "Fail to reset to commit hash {}. Error: {}",
+                    last_commit_hash, err
+                ))
+            })?;
+
+        return Err(GinspError::Git(format!(
+            "Fail to cherry-pick commit {} {}",
+            short_sha, subject
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Attach the files changed by the commit, compared to its first parent.
+/// Counts of added and removed lines are left out with `name_only`.
+fn load_files(
+    git: &dyn git::GitBackend,
+    info: &mut CommitInfo,
+    name_only: bool,
+) -> anyhow::Result<()> {
+    let commit = &info.commit;
+    let mut files = git.diff_stat(commit.parents.first().map(String::as_str), &commit.sha)?;
+    if name_only {
+        for file in files.iter_mut() {
+            file.added = None;
+            file.removed = None;
+        }
+    }
+    info.files = Some(files);
+    Ok(())
+}
+
+/// Ticket id regex from the config file of the repository, if a project management is set up.
+fn read_ticket_id_regex(git: &dyn git::GitBackend) -> anyhow::Result<Option<Regex>> {
+    let repo_root = git.get_toplevel()?;
+    let config = Config::read_config_file_or_default(Some(repo_root.as_str()))?;
+    let regex = config
+        .project_management
+        .map(|project_management| compile_ticket_id_regex(&project_management.ticket_id_regex))
+        .transpose()?;
+    Ok(regex)
+}
+
+/// Ignore rules from the config file and the `.ginspignore` file of the repository.
+fn read_ignore_rules(git: &dyn git::GitBackend) -> anyhow::Result<IgnoreRules> {
+    let repo_root = git.get_toplevel()?;
+    let config = Config::read_config_file_or_default(Some(repo_root.as_str()))?;
+    let mut ignore_rules = IgnoreRules::from_config(&config.ignore)?;
+    ignore_rules.read_ignore_file(repo_root.as_str())?;
+    Ok(ignore_rules)
+}
+
+/// Remove the ignored commits, and count those that are not on the other side.
+fn remove_ignored(
+    commits: &indexmap::IndexMap<String, git::Commit>,
+    other: &indexmap::IndexMap<String, git::Commit>,
+    ignore_rules: &IgnoreRules,
+) -> (indexmap::IndexMap<String, git::Commit>, usize) {
+    if ignore_rules.is_empty() {
+        return (commits.clone(), 0);
+    }
+
+    let mut hidden = 0;
+    let commits = commits
+        .iter()
+        .filter(|(message, commit)| {
+            let is_ignored = ignore_rules.is_ignored(commit);
+            if is_ignored && !other.contains_key(*message) {
+                hidden += 1;
+            }
+            !is_ignored
+        })
+        .map(|(message, commit)| (message.to_string(), commit.clone()))
+        .collect();
+
+    (commits, hidden)
+}
+
+/// Split out the pairs of commits with similar messages, most similar pairs first,
+/// each commit matching at most one commit of the other side.
+fn split_fuzzy_matches(
+    source: Vec<CommitInfo>,
+    target: Vec<CommitInfo>,
+    threshold: f64,
+    ticket_id_regex: Option<&Regex>,
+) -> (Vec<CommitInfo>, Vec<CommitInfo>, Vec<FuzzyMatch>) {
+    let normalize = |commits: &[CommitInfo]| {
+        commits
+            .iter()
+            .map(|info| NormalizedSubject::new(&info.commit.subject, ticket_id_regex))
+            .collect::<Vec<_>>()
+    };
+    let source_subjects = normalize(&source);
+    let target_subjects = normalize(&target);
+
+    let mut candidates = vec![];
+    for (source_index, source_subject) in source_subjects.iter().enumerate() {
+        for (target_index, target_subject) in target_subjects.iter().enumerate() {
+            let similarity = source_subject.similarity(target_subject);
+            if similarity >= threshold {
+                candidates.push((similarity, source_index, target_index));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut source_match = vec![None; source.len()];
+    let mut target_matched = vec![false; target.len()];
+    for (similarity, source_index, target_index) in candidates {
+        if source_match[source_index].is_none() && !target_matched[target_index] {
+            source_match[source_index] = Some((target_index, similarity));
+            target_matched[target_index] = true;
+        }
+    }
+
+    let mut target = target.into_iter().map(Some).collect::<Vec<_>>();
+    let mut remaining_source = vec![];
+    let mut matches = vec![];
+    for (source_index, info) in source.into_iter().enumerate() {
+        match source_match[source_index] {
+            Some((target_index, similarity)) => matches.push(FuzzyMatch {
+                source: info,
+                target: target[target_index].take().expect("matched only once"),
+                similarity,
+            }),
+            None => remaining_source.push(info),
+        }
+    }
+    let remaining_target = target.into_iter().flatten().collect();
+
+    (remaining_source, remaining_target, matches)
+}
+
+/// Split out the commits that are reverted by a later commit in the list,
+/// recognized by the `This reverts commit <sha>` body line or a `Revert "<subject>"` subject.
+/// Commits are ordered newest first, so a revert of a revert pairs with the revert
+/// and leaves the original commit in the list.
+fn split_revert_pairs(commits: Vec<CommitInfo>) -> (Vec<CommitInfo>, Vec<RevertPair>) {
+    let mut reverted_by: Vec<Option<usize>> = vec![None; commits.len()];
+    let mut is_revert = vec![false; commits.len()];
+
+    for (index, info) in commits.iter().enumerate() {
+        if is_revert[index] || reverted_by[index].is_some() {
+            continue;
+        }
+
+        let reverted_sha = info.commit.reverted_sha();
+        let reverted_subject = info.commit.reverted_subject();
+        if reverted_sha.is_none() && reverted_subject.is_none() {
+            continue;
+        }
+
+        // the reverted commit is older, so it comes later in the list
+        let reverted = (index + 1..commits.len()).find(|&other| {
+            if is_revert[other] || reverted_by[other].is_some() {
+                return false;
+            }
+            let commit = &commits[other].commit;
+            reverted_sha.is_some_and(|sha| commit.sha.starts_with(sha))
+                || reverted_subject.is_some_and(|subject| commit.subject == subject)
+        });
+
+        if let Some(reverted) = reverted {
+            reverted_by[reverted] = Some(index);
+            is_revert[index] = true;
+        }
+    }
+
+    let mut reverts = commits
+        .iter()
+        .map(|_| None)
+        .collect::<Vec<Option<CommitInfo>>>();
+    let mut originals = vec![];
+    let mut remaining = vec![];
+    for (index, info) in commits.into_iter().enumerate() {
+        if is_revert[index] {
+            reverts[index] = Some(info);
+        } else if let Some(revert_index) = reverted_by[index] {
+            originals.push((info, revert_index));
+        } else {
+            remaining.push(info);
+        }
+    }
+
+    let pairs = originals
+        .into_iter()
+        .filter_map(|(commit, revert_index)| {
+            reverts[revert_index]
+                .take()
+                .map(|revert| RevertPair { commit, revert })
+        })
+        .collect();
+
+    (remaining, pairs)
+}
+
+/// Read the config file next to the workspace file in workspace mode,
+/// otherwise the repo-local config of the repository.
+pub(crate) fn read_config(cli: &Cli) -> anyhow::Result<Config> {
+    let config_root = match cli.workspace()? {
+        Some(workspace) => workspace.root.to_string_lossy().to_string(),
+        None => git::open(cli.repo.as_deref())?.get_toplevel()?,
+    };
+    Ok(Config::read_config_file(Some(config_root.as_str()))?)
+}
+
+pub(crate) fn project_management(config: &Config) -> Result<&ProjectManagement, GinspError> {
+    config.project_management.as_ref().ok_or(GinspError::Cli(
+        "Missing [project_management] in config file".to_string(),
+    ))
+}
+
+/// Add the repository name to errors in workspace mode.
+fn with_repository(err: anyhow::Error, name: &str, is_workspace: bool) -> anyhow::Error {
+    if is_workspace {
+        err.context(format!("Repository '{}'", name))
+    } else {
+        err
+    }
+}
+
+pub(crate) fn map_ticket_status(
+    commits: &mut [CommitInfo],
+    tracker: &dyn IssueTracker,
+    ticket_id_regex: &Regex,
+    tickets: &mut HashMap<String, Option<tracker::Ticket>>,
+    is_verbose: bool,
+) {
+    let ticket_numbers = commits
+        .iter()
+        .map(|commit| extract_ticket_number(&commit.commit.subject, ticket_id_regex))
+        .collect::<Vec<_>>();
+
+    fetch_tickets(
+        ticket_numbers.iter().flatten(),
+        tracker,
+        tickets,
+        is_verbose,
+    );
+
+    for (commit, ticket_number) in commits.iter_mut().zip(ticket_numbers) {
+        commit.ticket =
+            ticket_number.and_then(|ticket_number| tickets.get(&ticket_number).cloned().flatten());
+        commit.status = commit.ticket.as_ref().map(tracker::Ticket::status_label);
+    }
+}
+
+/// Keep only the chosen fields in the tickets of the commits, the status is always kept.
+fn select_ticket_fields(commits: &mut [CommitInfo], fields: &[TicketField]) {
+    let is_selected = |field| fields.contains(&field);
+    for ticket in commits
+        .iter_mut()
+        .filter_map(|commit| commit.ticket.as_mut())
+    {
+        if !is_selected(TicketField::Title) {
+            ticket.title = None;
+        }
+        if !is_selected(TicketField::Type) {
+            ticket.kind = None;
+        }
+        if !is_selected(TicketField::Priority) {
+            ticket.priority = None;
+        }
+        if !is_selected(TicketField::Assignee) {
+            ticket.assignee = None;
+        }
+        if !is_selected(TicketField::FixVersions) {
+            ticket.fix_versions.clear();
+        }
+        if !is_selected(TicketField::Labels) {
+            ticket.labels.clear();
+        }
+        if !is_selected(TicketField::Resolution) {
+            ticket.resolution = None;
+        }
+    }
+}
+
+/// Value of a ticket field of the commit, the items of list fields separated by `, `.
+pub(crate) fn ticket_field(info: &CommitInfo, field: TicketField) -> Option<String> {
+    if field == TicketField::Status {
+        return info.status.clone();
+    }
+
+    let ticket = info.ticket.as_ref()?;
+    let list = |items: &Vec<String>| Some(items.join(", ")).filter(|text| !text.is_empty());
+    match field {
+        TicketField::Status => None,
+        TicketField::Title => ticket.title.clone(),
+        TicketField::Type => ticket.kind.clone(),
+        TicketField::Priority => ticket.priority.clone(),
+        TicketField::Assignee => ticket.assignee.clone(),
+        TicketField::FixVersions => list(&ticket.fix_versions),
+        TicketField::Labels => list(&ticket.labels),
+        TicketField::Resolution => ticket.resolution.clone(),
+    }
+}
+
+/// Tickets shared across the repositories of a workspace are looked up by id,
+/// so an id like `#123` would be looked up in the configured repository for all of them.
+pub(crate) fn reject_relative_tickets<'a>(
+    tracker: &dyn IssueTracker,
+    repository: &str,
+    ticket_numbers: impl IntoIterator<Item = &'a String>,
+) -> anyhow::Result<(), GinspError> {
+    match ticket_numbers
+        .into_iter()
+        .find(|ticket_number| tracker.is_relative(ticket_number))
+    {
+        Some(ticket_number) => Err(GinspError::Cli(format!(
+            "[{}] Ticket {} does not name its repository and cannot be fetched in workspace mode, use -C instead of --workspace.",
+            repository, ticket_number
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Fetch the tickets that are not in `tickets` yet, each ticket once.
+/// Tickets that fail to be fetched are `None`.
+fn fetch_tickets<'a>(
+    ticket_numbers: impl IntoIterator<Item = &'a String>,
+    tracker: &dyn IssueTracker,
+    tickets: &mut HashMap<String, Option<tracker::Ticket>>,
+    is_verbose: bool,
+) {
+    let mut missing = ticket_numbers
+        .into_iter()
+        .filter(|ticket_number| !tickets.contains_key(*ticket_number))
+        .cloned()
+        .collect::<Vec<_>>();
+    missing.sort();
+    missing.dedup();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    if is_verbose {
+        for ticket_number in missing.iter() {
+            println!(
+                "Fetching ticket status for {} ({})",
+                ticket_number,
+                tracker.ticket_url(ticket_number)
+            );
+        }
+    }
+
+    for (ticket_number, result) in tracker.fetch_tickets(&missing) {
+        let ticket = match result {
+            Ok(ticket) => Some(ticket),
+            Err(err) => {
+                if is_verbose {
+                    eprintln!("Fail to fetch ticket {}. Error: {}", ticket_number, err);
+                }
+                None
+            }
+        };
+        tickets.insert(ticket_number, ticket);
+    }
+}
+
+/// Resolve the revisions to compare. With `--remote` the revisions are branches
+/// of the remote, fetched first with `--fetch`, and a shallow clone is deepened
+/// until the merge base of the branches is found.
+fn prepare_revisions(
+    git: &dyn git::GitBackend,
+    options: &cli::DiffMessageParams,
+) -> anyhow::Result<Vec<git::Revision>> {
+    let remote = match &options.remote {
+        Some(remote) => remote,
+        None => {
+            return options
+                .revisions
+                .iter()
+                .map(|rev| resolve_revision(git, rev))
+                .collect();
+        }
+    };
+
+    // fetch only the compared branches, into their remote-tracking refs
+    let refspecs = options
+        .revisions
+        .iter()
+        .map(|branch| format!("+refs/heads/{0}:refs/remotes/{1}/{0}", branch, remote))
+        .collect::<Vec<_>>();
+
+    if options.fetch {
+        if options.verbose {
+            println!("Fetching {} from {}", options.revisions.join(", "), remote);
+        }
+        git.fetch_refs(remote, &refspecs).map_err(|err| {
+            GinspError::Git(format!(
+                "Fail to fetch branches from remote '{}'. Error: {}",
+                remote, err
+            ))
+        })?;
+    }
+
+    let revisions = options
+        .revisions
+        .iter()
+        .map(|branch| resolve_revision(git, &format!("{}/{}", remote, branch)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    deepen_until_merge_base(git, remote, &refspecs, &revisions, options.verbose)?;
+
+    Ok(revisions)
+}
+
+/// Number of commits fetched by the first deepening of a shallow clone,
+/// doubled on each of the following ones.
+const DEEPEN_DEPTH: u32 = 50;
+/// Number of deepenings before fetching the whole history.
+const MAX_DEEPEN_STEPS: u32 = 5;
+
+fn deepen_until_merge_base(
+    git: &dyn git::GitBackend,
+    remote: &str,
+    refspecs: &[String],
+    revisions: &[git::Revision],
+    is_verbose: bool,
+) -> anyhow::Result<()> {
+    let has_merge_base = || {
+        revisions[1..]
+            .iter()
+            .all(|revision| git.merge_base(&revisions[0].sha, &revision.sha).is_ok())
+    };
+
+    let mut steps = 0;
+    while git.is_shallow()? && !has_merge_base() {
+        let depth = (steps < MAX_DEEPEN_STEPS).then(|| DEEPEN_DEPTH << steps);
+        if is_verbose {
+            match depth {
+                Some(depth) => println!("Merge base not found, deepening history by {}", depth),
+                None => println!("Merge base not found, fetching the whole history"),
+            }
+        }
+
+        git.deepen(remote, refspecs, depth).map_err(|err| {
+            GinspError::Git(format!(
+                "Fail to deepen history from remote '{}'. Error: {}",
+                remote, err
+            ))
+        })?;
+        steps += 1;
+    }
+
+    Ok(())
+}
+
+fn resolve_revision(git: &dyn git::GitBackend, rev: &str) -> anyhow::Result<git::Revision> {
+    git.resolve_revision(rev).map_err(|err| {
+        GinspError::Git(format!("Unknown revision '{}'. Error: {}", rev, err)).into()
+    })
+}
+
+fn load_commits_as_map(
+    git: &dyn git::GitBackend,
+    rev: &str,
+    log_options: &git::LogOptions,
+) -> Result<indexmap::IndexMap<String, git::Commit>, GinspError> {
+    let commits = git.log(rev, log_options).map_err(|err| {
+        GinspError::Git(format!(
+            "Fail to get commits info for revision '{}'. Error: {}",
+            rev, err
+        ))
+    })?;
+
+    let mut map = indexmap!();
+
+    for commit in commits.into_iter() {
+        map.insert(commit.subject.trim().to_string(), commit);
+    }
+
+    Ok(map)
+}
+
+fn unique_by_message<'a>(
+    from: &'a indexmap::IndexMap<String, git::Commit>,
+    to: &indexmap::IndexMap<String, git::Commit>,
+) -> Vec<&'a git::Commit> {
+    from.iter()
+        .filter(|(message, _)| !to.contains_key(*message))
+        .map(|(_, commit)| commit)
+        .collect::<Vec<_>>()
+}
+
+fn extract_ticket_number(message: &str, ticket_id_regex: &Regex) -> Option<String> {
+    let caps = ticket_id_regex.captures(message);
+    caps.map(|caps| caps[1].to_string())
+}
+
+/// Compile the ticket id regex of the config file, the ticket id is its first capture group.
+pub(crate) fn compile_ticket_id_regex(pattern: &str) -> anyhow::Result<Regex, GinspError> {
+    let invalid = |reason: String| {
+        GinspError::Config(ConfigErrorKind::InvalidTicketIdRegex(format!(
+            "'{}': {}",
+            pattern, reason
+        )))
+    };
+    let re = Regex::new(pattern).map_err(|err| invalid(err.to_string()))?;
+    if re.captures_len() < 2 {
+        return Err(invalid("capture the ticket id in a group".to_string()));
+    }
+    Ok(re)
+}
+
+/// Print fuzzy matches like this
+/// ```
+/// Probably the same (fuzzy match):
+/// ------------------------
+///   92% eec4f1c [ABC-10370] Fix null check
+///       54912eb ABC-10370: fix null check
+/// ```
+fn print_fuzzy_matches(matches: Vec<FuzzyMatch>) {
+    if matches.is_empty() {
+        return;
+    }
+
+    println!("\nProbably the same (fuzzy match):");
+    println!("------------------------");
+    for FuzzyMatch {
+        source,
+        target,
+        similarity,
+    } in matches
+    {
+        let similarity = format!("{:.0}%", similarity * 100.0);
+        println!(
+            "  {:>4} {} {}",
+            similarity, source.commit.short_sha, source.commit.subject
+        );
+        println!(
+            "  {:>4} {} {}",
+            "", target.commit.short_sha, target.commit.subject
+        );
+    }
+}
+
+fn print_repository_header(name: &str) {
+    println!("\n========================");
+    println!("Repository: {}", name);
+    println!("========================");
+}
+
+/// Print the files changed by a commit under it, with the added and removed lines if known.
+fn print_files(files: &[git::FileStat]) {
+    let counts = files
+        .iter()
+        .map(|file| match (file.added, file.removed) {
+            (Some(added), Some(removed)) => format!("+{} -{}", added, removed),
+            _ => "bin".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let has_counts = files.iter().any(|file| file.added.is_some());
+    let max_counts_len = counts.iter().map(String::len).max().unwrap_or(0);
+
+    for (file, counts) in files.iter().zip(counts) {
+        if has_counts {
+            println!(
+                "        {:width$} {}",
+                counts,
+                file.path,
+                width = max_counts_len
+            );
+        } else {
+            println!("        {}", file.path);
+        }
+    }
+}
+
+/// Print result as table like this
+/// ```
+/// Commit messages unique on branch (branch eec4f1c):
+/// ------------------------
+///     eec4f1c - [ABC-10370] message
+///     54912eb - [ABC-10365] message
+///     a4c5d6e - [ABC-10361] message (squashed into 9f8e7d6)
+///         +12 -3 src/payments.rs
+///         bin    assets/logo.png
+///   Cancelled by revert:
+///     1a2b3c4 [ABC-10360] message (reverted by 5d6e7f8)
+/// ```
+fn print_result(side: SideReport, ticket_fields: &[TicketField]) {
+    let SideReport {
+        revision,
+        unique: commits,
+        ignored,
+        reverted,
+    } = side;
+
+    println!(
+        "\nCommit messages unique on {} ({} {}):",
+        revision.name,
+        revision.kind,
+        &revision.sha[..revision.sha.len().min(7)]
+    );
+    println!("------------------------");
+    let commits_len = commits.len();
+    let max_len_index = commits_len.to_string().len();
+    // one column per ticket field, padded to its longest value
+    let ticket_columns = ticket_fields
+        .iter()
+        .map(|field| {
+            let values = commits
+                .iter()
+                .map(|commit| ticket_field(commit, *field).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let width = values.iter().map(String::len).max().unwrap_or(0);
+            (values, width)
+        })
+        .collect::<Vec<_>>();
+    for (index, item) in commits.into_iter().enumerate() {
+        let CommitInfo {
+            commit,
+            is_picked,
+            squashed_into,
+            files,
+            ..
+        } = item;
+
+        let mut string_vec = vec![];
+
+        if is_picked {
+            string_vec.push("*".to_string());
+        } else {
+            string_vec.push(" ".to_string());
+        }
+
+        string_vec.push(format!(
+            "{:width$}",
+            commits_len - index,
+            width = max_len_index
+        ));
+
+        for (values, width) in ticket_columns.iter() {
+            string_vec.push(format!("{:width$}", values[index], width = width));
+        }
+
+        string_vec.push(commit.short_sha);
+        string_vec.push(commit.subject);
+
+        if let Some(squashed_into) = squashed_into {
+            string_vec.push(format!("(squashed into {})", squashed_into));
+        }
+
+        println!("{}", string_vec.join(" "));
+
+        if let Some(files) = files {
+            print_files(&files);
+        }
+    }
+
+    if !reverted.is_empty() {
+        println!("  Cancelled by revert:");
+        for RevertPair { commit, revert } in reverted {
+            println!(
+                "    {} {} (reverted by {})",
+                commit.commit.short_sha, commit.commit.subject, revert.commit.short_sha
+            );
+        }
+    }
+
+    if ignored > 0 {
+        println!(
+            "  ({} ignored commits hidden, use --no-ignore to show them)",
+            ignored
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_revert_pairs, CommitInfo};
+    use crate::git::Commit;
+
+    fn commit(sha: &str, subject: &str, body: &str) -> CommitInfo {
+        CommitInfo::from(&Commit::with_message(sha, subject, body))
+    }
+
+    fn shas(commits: &[CommitInfo]) -> Vec<&str> {
+        commits
+            .iter()
+            .map(|info| info.commit.sha.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn split_revert_pairs_pairs_a_commit_with_its_revert() {
+        let (remaining, pairs) = split_revert_pairs(vec![
+            commit(
+                "c333333",
+                "Revert \"Add login\"",
+                "This reverts commit a111111.",
+            ),
+            commit("b222222", "Fix typo", ""),
+            commit("a111111", "Add login", ""),
+        ]);
+
+        assert_eq!(shas(&remaining), ["b222222"]);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].commit.commit.sha, "a111111");
+        assert_eq!(pairs[0].revert.commit.sha, "c333333");
+    }
+
+    #[test]
+    fn split_revert_pairs_pairs_a_revert_of_a_revert_with_the_revert() {
+        let (remaining, pairs) = split_revert_pairs(vec![
+            commit(
+                "c333333",
+                "Reapply \"Add login\"",
+                "This reverts commit b222222.",
+            ),
+            commit(
+                "b222222",
+                "Revert \"Add login\"",
+                "This reverts commit a111111.",
+            ),
+            commit("a111111", "Add login", ""),
+        ]);
+
+        assert_eq!(shas(&remaining), ["a111111"]);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].commit.commit.sha, "b222222");
+        assert_eq!(pairs[0].revert.commit.sha, "c333333");
+    }
+
+    #[test]
+    fn split_revert_pairs_keeps_a_revert_of_a_commit_not_in_the_list() {
+        let (remaining, pairs) = split_revert_pairs(vec![
+            commit(
+                "b222222",
+                "Revert \"Add login\"",
+                "This reverts commit a111111.",
+            ),
+            commit("d444444", "Fix typo", ""),
+        ]);
+
+        assert_eq!(shas(&remaining), ["b222222", "d444444"]);
+        assert!(pairs.is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::cli::diff_message::{ticket_field, CommitInfo, RepositoryReport};
+use crate::cli::TicketField;
+use crate::error::GinspError;
+use crate::git;
+
+/// Print the reports as a JSON array, one object per repository.
+pub fn print_json<T: serde::Serialize>(reports: &[T]) -> anyhow::Result<()> {
+    let json =
+        serde_json::to_string_pretty(reports).map_err(|err| GinspError::System(err.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Print the reports as CSV, one line per commit.
+/// `state` is `unique`, `reverted`, `revert` (the commit that cancels a reverted one),
+/// or `fuzzy_match` (the two commits of a fuzzy match follow each other).
+/// With `--stat` or `--name-only`, `files` lists the changed paths separated by `;`
+/// and `added` and `removed` are the line counts summed over the files.
+/// The ticket fields are the columns between `subject` and `picked`, `status` by default.
+pub fn print_csv(
+    reports: &[RepositoryReport],
+    ticket_fields: &[TicketField],
+) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    let mut header = vec![
+        "repository",
+        "revision",
+        "state",
+        "sha",
+        "short_sha",
+        "author",
+        "author_date",
+        "subject",
+    ];
+    header.extend(ticket_fields.iter().map(|field| column_name(*field)));
+    header.extend(["picked", "squashed_into", "files", "added", "removed"]);
+    writer.write_record(header)?;
+
+    for report in reports.iter() {
+        for side in [&report.diff.source, &report.diff.target] {
+            let rows = side.unique.iter().map(|commit| ("unique", commit)).chain(
+                side.reverted
+                    .iter()
+                    .flat_map(|pair| [("reverted", &pair.commit), ("revert", &pair.revert)]),
+            );
+
+            for (state, commit) in rows {
+                writer.write_record(commit_record(
+                    &report.name,
+                    &side.revision.name,
+                    state,
+                    commit,
+                    ticket_fields,
+                ))?;
+            }
+        }
+
+        for fuzzy_match in report.diff.fuzzy_matches.iter() {
+            let source = &report.diff.source.revision.name;
+            let target = &report.diff.target.revision.name;
+            writer.write_record(commit_record(
+                &report.name,
+                source,
+                "fuzzy_match",
+                &fuzzy_match.source,
+                ticket_fields,
+            ))?;
+            writer.write_record(commit_record(
+                &report.name,
+                target,
+                "fuzzy_match",
+                &fuzzy_match.target,
+                ticket_fields,
+            ))?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn commit_record(
+    repository: &str,
+    revision: &str,
+    state: &str,
+    info: &CommitInfo,
+    ticket_fields: &[TicketField],
+) -> Vec<String> {
+    let commit = &info.commit;
+    let files = info.files.as_ref();
+    let mut record = vec![
+        repository.to_string(),
+        revision.to_string(),
+        state.to_string(),
+        commit.sha.to_string(),
+        commit.short_sha.to_string(),
+        format!("{} <{}>", commit.author.name, commit.author.email),
+        commit.author_date.to_rfc3339(),
+        commit.subject.to_string(),
+    ];
+    record.extend(
+        ticket_fields
+            .iter()
+            .map(|field| ticket_field(info, *field).unwrap_or_default()),
+    );
+    record.extend([
+        info.is_picked.to_string(),
+        info.squashed_into.clone().unwrap_or_default(),
+        files
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|file| file.path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(";")
+            })
+            .unwrap_or_default(),
+        line_count(files, |file| file.added),
+        line_count(files, |file| file.removed),
+    ]);
+    record
+}
+
+fn column_name(field: TicketField) -> &'static str {
+    match field {
+        TicketField::Status => "status",
+        TicketField::Title => "title",
+        TicketField::Type => "type",
+        TicketField::Priority => "priority",
+        TicketField::Assignee => "assignee",
+        TicketField::FixVersions => "fix_versions",
+        TicketField::Labels => "labels",
+        TicketField::Resolution => "resolution",
+    }
+}
+
+/// Sum of the line counts of the files, empty when no file has one.
+fn line_count(
+    files: Option<&Vec<git::FileStat>>,
+    count: impl Fn(&git::FileStat) -> Option<usize>,
+) -> String {
+    files
+        .into_iter()
+        .flatten()
+        .filter_map(count)
+        .reduce(|a, b| a + b)
+        .map(|sum| sum.to_string())
+        .unwrap_or_default()
+}
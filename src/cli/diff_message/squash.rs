@@ -0,0 +1,168 @@
+use crate::cli::diff_message::{extract_ticket_number, CommitInfo};
+use crate::git;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// Longest run of source commits compared against one squash commit.
+const MAX_SQUASH_LENGTH: usize = 20;
+
+/// Mark source commits that a single target commit squashes, with the short hash of
+/// the target commit. Both lists are newest first, as returned by `git log`.
+///
+/// A run of consecutive source commits matches when their combined diff has the same
+/// patch id as the target commit. Otherwise, when a ticket regex is given, the source
+/// commits of the target commit's ticket match when they change the same files and
+/// leave them with the same contents as the target commit.
+pub fn mark_squashed(
+    git: &dyn git::GitBackend,
+    source: &mut [CommitInfo],
+    target: &[CommitInfo],
+    ticket_id_regex: Option<&Regex>,
+) -> anyhow::Result<()> {
+    let mut source_files: Vec<Option<HashSet<String>>> = vec![None; source.len()];
+
+    for squash in target.iter().map(|info| &info.commit) {
+        // merge and root commits have no single parent to diff against
+        let [parent] = squash.parents.as_slice() else {
+            continue;
+        };
+        let Some(squash_patch_id) = git.patch_id(parent, &squash.sha)? else {
+            continue;
+        };
+        let squash_files = git
+            .changed_files(parent, &squash.sha)?
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        let mut matched = find_squashed_run(
+            git,
+            source,
+            &mut source_files,
+            &squash_files,
+            &squash_patch_id,
+        )?;
+        if matched.is_none() {
+            if let Some(ticket_id_regex) = ticket_id_regex {
+                if let Some(ticket) = extract_ticket_number(&squash.subject, ticket_id_regex) {
+                    matched = find_squashed_ticket(
+                        git,
+                        source,
+                        &mut source_files,
+                        squash,
+                        &squash_files,
+                        &ticket,
+                        ticket_id_regex,
+                    )?;
+                }
+            }
+        }
+
+        for index in matched.unwrap_or_default() {
+            source[index].squashed_into = Some(squash.short_sha.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Indexes of a run of at least 2 consecutive source commits whose combined diff
+/// has the given patch id.
+fn find_squashed_run(
+    git: &dyn git::GitBackend,
+    source: &[CommitInfo],
+    source_files: &mut [Option<HashSet<String>>],
+    squash_files: &HashSet<String>,
+    squash_patch_id: &str,
+) -> anyhow::Result<Option<Vec<usize>>> {
+    for newest in 0..source.len() {
+        let mut files = HashSet::new();
+
+        for oldest in newest..source.len().min(newest + MAX_SQUASH_LENGTH) {
+            let commit = &source[oldest].commit;
+            if source[oldest].squashed_into.is_some() || commit.parents.len() != 1 {
+                break;
+            }
+            // the run must be a chain of parents for its combined diff to make sense
+            if oldest > newest && source[oldest - 1].commit.parents[0] != commit.sha {
+                break;
+            }
+
+            let commit_files = changed_files(git, source, source_files, oldest)?;
+            if !commit_files.is_subset(squash_files) {
+                break;
+            }
+            files.extend(commit_files.iter().cloned());
+
+            if oldest > newest && files == *squash_files {
+                let patch_id = git.patch_id(&commit.parents[0], &source[newest].commit.sha)?;
+                if patch_id.as_deref() == Some(squash_patch_id) {
+                    return Ok(Some((newest..=oldest).collect()));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Indexes of the source commits of the ticket when together they change the same files
+/// as the squash commit, and the newest of them has the same contents for those files.
+fn find_squashed_ticket(
+    git: &dyn git::GitBackend,
+    source: &[CommitInfo],
+    source_files: &mut [Option<HashSet<String>>],
+    squash: &git::Commit,
+    squash_files: &HashSet<String>,
+    ticket: &str,
+    ticket_id_regex: &Regex,
+) -> anyhow::Result<Option<Vec<usize>>> {
+    let group = source
+        .iter()
+        .enumerate()
+        .filter(|(_, info)| info.squashed_into.is_none() && info.commit.parents.len() == 1)
+        .filter(|(_, info)| {
+            extract_ticket_number(&info.commit.subject, ticket_id_regex).as_deref() == Some(ticket)
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    if group.len() < 2 {
+        return Ok(None);
+    }
+
+    let mut files = HashSet::new();
+    for index in group.iter() {
+        files.extend(
+            changed_files(git, source, source_files, *index)?
+                .iter()
+                .cloned(),
+        );
+    }
+    if files != *squash_files {
+        return Ok(None);
+    }
+
+    let newest = &source[group[0]].commit.sha;
+    for file in files.iter() {
+        if git.file_blob(newest, file)? != git.file_blob(&squash.sha, file)? {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(group))
+}
+
+/// Files changed by the source commit at the index, loaded once.
+fn changed_files<'a>(
+    git: &dyn git::GitBackend,
+    source: &[CommitInfo],
+    source_files: &'a mut [Option<HashSet<String>>],
+    index: usize,
+) -> anyhow::Result<&'a HashSet<String>> {
+    if source_files[index].is_none() {
+        let commit = &source[index].commit;
+        let files = git.changed_files(&commit.parents[0], &commit.sha)?;
+        source_files[index] = Some(files.into_iter().collect());
+    }
+    Ok(source_files[index].get_or_insert_with(HashSet::new))
+}
@@ -1,14 +1,37 @@
 pub(crate) mod diagnostic;
 pub(crate) mod diff_message;
+pub(crate) mod pick;
 pub(crate) mod update;
 pub(crate) mod version;
 
-use clap::Parser;
+use crate::workspace::{Repository, Workspace, DEFAULT_WORKSPACE_FILE};
+use crate::{git, tracker};
+use clap::{Args, Parser, ValueEnum};
+use std::path::PathBuf;
 
 /// Small utils tools to update local git and compare the commits.
 #[derive(Parser, Debug)]
 #[clap(name = "ginsp")]
 pub struct Cli {
+    /// Run as if ginsp was started in <path> instead of the current directory.
+    /// The path is passed to every git invocation and used to find the repo-local config.
+    #[clap(short = 'C', long = "repo", global = true, value_name = "path")]
+    pub repo: Option<PathBuf>,
+
+    /// Run `update` and `diff-message` across every repository listed in a workspace file.
+    /// Defaults to `ginsp-workspace.toml` when no file is given (`--workspace=<file>`).
+    #[clap(
+        short = 'w',
+        long = "workspace",
+        global = true,
+        value_name = "file",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = DEFAULT_WORKSPACE_FILE,
+        conflicts_with = "repo"
+    )]
+    pub workspace: Option<PathBuf>,
+
     #[clap(subcommand)]
     pub subcommand: SubCommand,
 }
@@ -24,10 +47,14 @@ pub enum SubCommand {
     #[clap(name = "update", alias = "u")]
     Update(Update),
 
-    /// Compare two branches by commit messages.
+    /// Compare two or more branches, tags, or commits by commit messages.
     #[clap(name = "diff-message", alias = "dm")]
     DiffMessage(DiffMessageParams),
 
+    /// Choose the commits unique on a revision to cherry-pick onto the current branch.
+    #[clap(name = "pick", alias = "p")]
+    Pick(PickParams),
+
     /// Diagnostic command to check if the tool is working.
     #[clap(name = "diagnostic", alias = "dia")]
     Diagnostic,
@@ -46,9 +73,29 @@ pub struct Update {
 
 #[derive(Parser, Debug)]
 pub struct DiffMessageParams {
-    /// Two branches to compare.
-    #[clap(name = "branches", required = true)]
-    pub branches: Vec<String>,
+    /// Two revisions to compare: branches, remote branches, tags, or commits (e.g. `HEAD~50`).
+    /// With more than two revisions, print a matrix of which commits are on which revision.
+    #[clap(name = "revisions", required = true)]
+    pub revisions: Vec<String>,
+
+    /// Compare the branches of the remote instead of local branches,
+    /// e.g. `--remote origin main release-x` compares `origin/main` and `origin/release-x`.
+    /// Missing history of shallow clones is fetched until the merge base is found.
+    #[clap(long, value_name = "name")]
+    pub remote: Option<String>,
+
+    /// Fetch the compared branches from the remote before comparing. Requires `--remote`.
+    #[clap(long, default_value = "false", requires = "remote")]
+    pub fetch: bool,
+
+    /// Output format of the result.
+    #[clap(long, value_enum, default_value = "table")]
+    pub format: OutputFormat,
+
+    /// Rows of the matrix when comparing more than two branches:
+    /// one row per commit message, or one row per ticket id (requires a config file).
+    #[clap(long = "rows", value_enum, default_value = "commit")]
+    pub matrix_rows: MatrixRows,
 
     /// `cherry-pick` commits that contains the given string.
     /// Multiple strings can be separated by comma.
@@ -62,10 +109,175 @@ pub struct DiffMessageParams {
     #[clap(short = 't', long = "ticket-status", default_value = "false")]
     pub is_fetch_ticket_status: bool,
 
+    #[clap(flatten)]
+    pub ticket_cache: TicketCacheOptions,
+
+    /// Ticket fields shown with `-t`, separated by comma, e.g. `status,priority,fix-versions`.
+    /// The fields are columns of the table and the CSV output, and of the ticket in JSON.
+    #[clap(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "status",
+        requires = "is_fetch_ticket_status"
+    )]
+    pub ticket_fields: Vec<TicketField>,
+
+    /// Match reworded commits by similar messages (ignoring case, punctuation, and ticket prefixes)
+    /// and report them separately as probably the same. Fuzzy matches are not cherry-picked.
+    /// The optional threshold between 0 and 1 defaults to 0.85 (`--fuzzy=0.9`).
+    #[clap(
+        long,
+        value_name = "threshold",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "0.85",
+        value_parser = crate::fuzzy::parse_threshold
+    )]
+    pub fuzzy: Option<f64>,
+
+    /// Detect target commits that squash several source commits, by the patch id of their
+    /// combined diff or by ticket id and file contents. The squashed source commits are
+    /// marked with the target commit and are not cherry-picked.
+    #[clap(long = "detect-squash", default_value = "false")]
+    pub detect_squash: bool,
+
+    /// Treat a commit and its revert as regular commits instead of a cancelled pair.
+    /// Without this option cancelled pairs are never cherry-picked.
+    #[clap(long = "keep-reverts", default_value = "false")]
+    pub keep_reverts: bool,
+
+    /// Show the files changed by each commit with the lines added and removed.
+    #[clap(long = "stat", default_value = "false", conflicts_with = "name_only")]
+    pub stat: bool,
+
+    /// Show the files changed by each commit.
+    #[clap(long = "name-only", default_value = "false")]
+    pub name_only: bool,
+
+    #[clap(flatten)]
+    pub filters: CommitFilters,
+
+    #[clap(short, long, default_value = "false")]
+    pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct PickParams {
+    /// Revision to pick the commits from.
+    #[clap(name = "source")]
+    pub source: String,
+
+    /// Branch to pick the commits onto, which has to be the current branch.
+    #[clap(name = "target")]
+    pub target: String,
+
+    /// Choose the commits in a terminal UI: toggle, filter, preview, and reorder them.
+    #[clap(short = 'i', long = "interactive", default_value = "false")]
+    pub interactive: bool,
+
+    /// Fetching ticket status from project management tool
+    /// and show it next to each commit. This option requires a config file.
+    #[clap(short = 't', long = "ticket-status", default_value = "false")]
+    pub is_fetch_ticket_status: bool,
+
+    #[clap(flatten)]
+    pub ticket_cache: TicketCacheOptions,
+
+    #[clap(flatten)]
+    pub filters: CommitFilters,
+
     #[clap(short, long, default_value = "false")]
     pub verbose: bool,
 }
 
+/// Filters of the compared commits, shared by `diff-message` and `pick`.
+#[derive(Args, Clone, Debug)]
+pub struct CommitFilters {
+    /// Only include commits whose author matches the pattern (`git log --author`).
+    #[clap(long, value_name = "pattern")]
+    pub author: Option<String>,
+
+    /// Only include commits more recent than the date (`git log --since`).
+    #[clap(long, value_name = "date")]
+    pub since: Option<String>,
+
+    /// Only include commits older than the date (`git log --until`).
+    #[clap(long, value_name = "date")]
+    pub until: Option<String>,
+
+    /// Do not apply the ignore rules from the config file and `.ginspignore`.
+    #[clap(long = "no-ignore", default_value = "false")]
+    pub no_ignore: bool,
+
+    /// Only include commits touching the given paths.
+    /// For example: `ginsp diff-message master develop -- services/payments/`
+    #[clap(name = "pathspec", last = true)]
+    pub paths: Vec<String>,
+}
+
+impl CommitFilters {
+    /// The `git log` options of the filters.
+    pub fn log_options(&self) -> git::LogOptions {
+        git::LogOptions {
+            author: self.author.clone(),
+            since: self.since.clone(),
+            until: self.until.clone(),
+            paths: self.paths.clone(),
+        }
+    }
+}
+
+/// Ticket cache options of the commands fetching ticket statuses with `-t`.
+#[derive(Args, Clone, Debug)]
+pub struct TicketCacheOptions {
+    /// Fetch every ticket again instead of using the ticket cache.
+    #[clap(long, requires = "is_fetch_ticket_status")]
+    pub refresh_tickets: bool,
+
+    /// Use only the ticket cache, without requests to the project management tool.
+    /// Tickets cached longer than the TTL are marked as stale.
+    #[clap(
+        long,
+        requires = "is_fetch_ticket_status",
+        conflicts_with = "refresh_tickets"
+    )]
+    pub offline: bool,
+}
+
+impl TicketCacheOptions {
+    pub fn cache_mode(&self) -> tracker::CacheMode {
+        tracker::CacheMode::new(self.refresh_tickets, self.offline)
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+/// Field of a ticket shown with `-t`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum TicketField {
+    Status,
+    #[value(alias = "summary")]
+    Title,
+    Type,
+    Priority,
+    Assignee,
+    FixVersions,
+    Labels,
+    Resolution,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum MatrixRows {
+    Commit,
+    Ticket,
+}
+
 impl Cli {
     pub fn run() -> anyhow::Result<()> {
         let options = Cli::parse();
@@ -80,6 +292,9 @@ impl Cli {
             SubCommand::DiffMessage(_) => {
                 diff_message::DiffMessage::new().execute(&options)?;
             }
+            SubCommand::Pick(_) => {
+                pick::Pick::new().execute(&options)?;
+            }
             SubCommand::Diagnostic => {
                 diagnostic::Diagnostic::new().execute(&options)?;
             }
@@ -87,6 +302,29 @@ impl Cli {
 
         Ok(())
     }
+
+    /// Load the workspace file given by `--workspace`, if any.
+    pub fn workspace(&self) -> anyhow::Result<Option<Workspace>> {
+        match &self.workspace {
+            Some(path) => Ok(Some(Workspace::read_toml_file(path)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Repositories the command runs against: every repository of the workspace,
+    /// or the single repository given by `-C` (or the current directory).
+    pub fn repositories(&self) -> anyhow::Result<Vec<Repository>> {
+        match self.workspace()? {
+            Some(workspace) => Ok(workspace.repositories()),
+            None => Ok(vec![Repository {
+                name: self
+                    .repo
+                    .as_ref()
+                    .map_or(".".to_string(), |path| path.to_string_lossy().to_string()),
+                path: self.repo.clone(),
+            }]),
+        }
+    }
 }
 
 pub trait CommandHandler {
@@ -0,0 +1,112 @@
+mod picker;
+
+use crate::cli::diff_message::{
+    check_pick_target, cherry_pick_commits, compile_ticket_id_regex, diff_repository,
+    map_ticket_status, project_management, read_config, CommitInfo,
+};
+use crate::cli::{Cli, CommandHandler, DiffMessageParams, PickParams};
+use crate::error::GinspError;
+use crate::{cli, git, tracker};
+use clap::Parser;
+use std::collections::HashMap;
+
+pub struct Pick {}
+
+impl Pick {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CommandHandler for Pick {
+    fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        let options = match cli.subcommand {
+            cli::SubCommand::Pick(ref pick_cmd) => pick_cmd,
+            _ => return Err(GinspError::Cli("Invalid subcommand".to_string()).into()),
+        };
+
+        if !options.interactive {
+            return Err(GinspError::Cli(
+                "Use -i to choose the commits to pick, or `diff-message -c` to pick by message."
+                    .to_string(),
+            )
+            .into());
+        }
+
+        if cli.workspace.is_some() {
+            return Err(GinspError::Cli(
+                "Pick works on a single repository, use -C instead of --workspace.".to_string(),
+            )
+            .into());
+        }
+
+        git::validate_revisions(&[options.source.to_string(), options.target.to_string()])?;
+
+        let git = git::open(cli.repo.as_deref())?;
+        let git = git.as_ref();
+
+        // the commits are listed like `diff-message`, then picked by the same engine
+        let diff = diff_repository(git, &diff_message_params(options)?)?;
+        check_pick_target(git, &diff.target.revision)?;
+
+        let mut commits = diff.source.unique;
+        if commits.is_empty() {
+            println!(
+                "No commits unique on {} to pick onto {}.",
+                diff.source.revision.name, diff.target.revision.name
+            );
+            return Ok(());
+        }
+
+        if options.is_fetch_ticket_status {
+            let profile = read_config(cli)?;
+            let project_management = project_management(&profile)?;
+            let tracker = tracker::open_cached(
+                project_management,
+                options.ticket_cache.cache_mode(),
+                options.verbose,
+            )?;
+            map_ticket_status(
+                &mut commits,
+                tracker.as_ref(),
+                &compile_ticket_id_regex(&project_management.ticket_id_regex)?,
+                &mut HashMap::new(),
+                options.verbose,
+            );
+        }
+
+        // listed and picked oldest first
+        commits.reverse();
+
+        let Some(order) = picker::run(git, &commits)? else {
+            println!("Cancelled, nothing was picked.");
+            return Ok(());
+        };
+
+        let mut chosen = order
+            .into_iter()
+            .map(|index| commits[index].clone())
+            .collect::<Vec<_>>();
+        cherry_pick_commits(git, chosen.iter_mut(), options.verbose)?;
+
+        println!("\nPicked onto {}:", diff.target.revision.name);
+        println!("------------------------");
+        for CommitInfo { commit, .. } in chosen.iter() {
+            println!("  {} {}", commit.short_sha, commit.subject);
+        }
+        println!();
+
+        Ok(())
+    }
+}
+
+/// Options of `diff-message` that list the same commits as the pick options,
+/// with the defaults of the other options.
+fn diff_message_params(options: &PickParams) -> anyhow::Result<DiffMessageParams, GinspError> {
+    let mut params =
+        DiffMessageParams::try_parse_from(["diff-message", &options.source, &options.target])
+            .map_err(|err| GinspError::Cli(err.to_string()))?;
+    params.filters = options.filters.clone();
+    params.verbose = options.verbose;
+    Ok(params)
+}
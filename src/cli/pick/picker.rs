@@ -0,0 +1,312 @@
+use crate::cli::diff_message::CommitInfo;
+use crate::git;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::HashMap;
+
+const HELP: &str = "↑/↓ move  space toggle  a toggle all  / filter  p preview  \
+                    J/K reorder  enter pick  q cancel";
+const FILTER_HELP: &str = "type to filter  enter keep filter  esc clear filter";
+
+/// Let the user choose the commits to pick in a terminal UI.
+/// Returns the indexes of the chosen commits in the order to pick them,
+/// or `None` when the user cancelled.
+pub fn run(
+    git: &dyn git::GitBackend,
+    commits: &[CommitInfo],
+) -> anyhow::Result<Option<Vec<usize>>> {
+    let mut picker = Picker::new(commits);
+    let mut terminal = ratatui::try_init()?;
+    let result = picker.event_loop(&mut terminal, git);
+    ratatui::restore();
+    result
+}
+
+struct Picker<'a> {
+    commits: &'a [CommitInfo],
+    /// Indexes into `commits` in the order they are listed and picked.
+    order: Vec<usize>,
+    /// Whether each of `commits` is chosen.
+    selected: Vec<bool>,
+    filter: String,
+    is_filtering: bool,
+    /// Position of the cursor in the visible rows.
+    cursor: usize,
+    is_previewing: bool,
+    preview_scroll: u16,
+    /// `git show` output of the previewed commits, loaded once.
+    previews: HashMap<usize, String>,
+}
+
+enum Action {
+    Continue,
+    Confirm,
+    Cancel,
+}
+
+impl<'a> Picker<'a> {
+    fn new(commits: &'a [CommitInfo]) -> Self {
+        Self {
+            commits,
+            order: (0..commits.len()).collect(),
+            selected: vec![false; commits.len()],
+            filter: String::new(),
+            is_filtering: false,
+            cursor: 0,
+            is_previewing: false,
+            preview_scroll: 0,
+            previews: HashMap::new(),
+        }
+    }
+
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        git: &dyn git::GitBackend,
+    ) -> anyhow::Result<Option<Vec<usize>>> {
+        loop {
+            if self.is_previewing {
+                self.load_preview(git);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let action = if self.is_filtering {
+                self.handle_filter_key(key)
+            } else {
+                self.handle_key(key)
+            };
+
+            match action {
+                Action::Continue => {}
+                Action::Confirm => return Ok(Some(self.chosen())),
+                Action::Cancel => return Ok(None),
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Cancel;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Cancel,
+            KeyCode::Enter => return Action::Confirm,
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Char('K') => self.move_row(-1),
+            KeyCode::Char('J') => self.move_row(1),
+            KeyCode::Char(' ') => self.toggle(),
+            KeyCode::Char('a') => self.toggle_all(),
+            KeyCode::Char('/') => self.is_filtering = true,
+            KeyCode::Char('p') => {
+                self.is_previewing = !self.is_previewing;
+                self.preview_scroll = 0;
+            }
+            KeyCode::PageDown => self.preview_scroll = self.preview_scroll.saturating_add(10),
+            KeyCode::PageUp => self.preview_scroll = self.preview_scroll.saturating_sub(10),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn handle_filter_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => self.is_filtering = false,
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.is_filtering = false;
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            KeyCode::Char(c) => self.filter.push(c),
+            _ => {}
+        }
+        self.cursor = self.cursor.min(self.visible().len().saturating_sub(1));
+        self.preview_scroll = 0;
+        Action::Continue
+    }
+
+    /// Positions in `order` of the rows matching the filter.
+    fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.to_lowercase();
+        self.order
+            .iter()
+            .enumerate()
+            .filter(|(_, index)| {
+                let info = &self.commits[**index];
+                filter.is_empty()
+                    || info.commit.subject.to_lowercase().contains(&filter)
+                    || info.commit.short_sha.contains(&filter)
+                    || info
+                        .status
+                        .as_ref()
+                        .is_some_and(|status| status.to_lowercase().contains(&filter))
+            })
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Index into `commits` of the row under the cursor.
+    fn current(&self) -> Option<usize> {
+        self.visible()
+            .get(self.cursor)
+            .map(|position| self.order[*position])
+    }
+
+    fn move_cursor(&mut self, offset: isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            return;
+        }
+        self.cursor = self.cursor.saturating_add_signed(offset).min(len - 1);
+        self.preview_scroll = 0;
+    }
+
+    /// Move the row under the cursor before or after the next visible row.
+    fn move_row(&mut self, offset: isize) {
+        let visible = self.visible();
+        let Some(other) = self.cursor.checked_add_signed(offset) else {
+            return;
+        };
+        if other >= visible.len() {
+            return;
+        }
+        self.order.swap(visible[self.cursor], visible[other]);
+        self.cursor = other;
+    }
+
+    fn toggle(&mut self) {
+        if let Some(index) = self.current() {
+            self.selected[index] = !self.selected[index];
+        }
+    }
+
+    /// Select every visible row, or unselect them when they are all selected.
+    fn toggle_all(&mut self) {
+        let visible = self
+            .visible()
+            .into_iter()
+            .map(|position| self.order[position])
+            .collect::<Vec<_>>();
+        let is_all_selected = visible.iter().all(|index| self.selected[*index]);
+        for index in visible {
+            self.selected[index] = !is_all_selected;
+        }
+    }
+
+    fn chosen(&self) -> Vec<usize> {
+        self.order
+            .iter()
+            .copied()
+            .filter(|index| self.selected[*index])
+            .collect()
+    }
+
+    fn load_preview(&mut self, git: &dyn git::GitBackend) {
+        let Some(index) = self.current() else {
+            return;
+        };
+        self.previews.entry(index).or_insert_with(|| {
+            git.show(&self.commits[index].commit.sha)
+                .unwrap_or_else(|err| err.to_string())
+        });
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        if self.is_previewing {
+            let [list, preview] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(main);
+            self.draw_list(frame, list);
+            self.draw_preview(frame, preview);
+        } else {
+            self.draw_list(frame, main);
+        }
+
+        let footer_text = if self.is_filtering {
+            Line::from(vec![
+                Span::raw(format!("/{}", self.filter)),
+                Span::raw("  "),
+                Span::raw(FILTER_HELP).dim(),
+            ])
+        } else {
+            Line::from(HELP).dim()
+        };
+        frame.render_widget(Paragraph::new(footer_text), footer);
+    }
+
+    fn draw_list(&self, frame: &mut Frame, area: Rect) {
+        let visible = self.visible();
+        let max_status_len = self
+            .commits
+            .iter()
+            .map(|info| info.status.as_ref().map_or(0, |s| s.chars().count()))
+            .max()
+            .unwrap_or(0);
+
+        let items = visible
+            .iter()
+            .map(|position| {
+                let index = self.order[*position];
+                let info = &self.commits[index];
+                let mark = if self.selected[index] { "[x]" } else { "[ ]" };
+                let mut spans = vec![Span::raw(mark), Span::raw(" ")];
+                if max_status_len > 0 {
+                    spans.push(Span::raw(format!(
+                        "{:width$} ",
+                        info.status.clone().unwrap_or_default(),
+                        width = max_status_len
+                    )));
+                }
+                spans.push(Span::raw(info.commit.short_sha.to_string()).yellow());
+                spans.push(Span::raw(" "));
+                spans.push(Span::raw(info.commit.subject.to_string()));
+                ListItem::new(Line::from(spans))
+            })
+            .collect::<Vec<_>>();
+
+        let selected_count = self.selected.iter().filter(|selected| **selected).count();
+        let mut title = format!(
+            " Commits to pick, oldest first ({} of {} selected) ",
+            selected_count,
+            self.commits.len()
+        );
+        if !self.filter.is_empty() {
+            title.push_str(&format!("filter: {} ", self.filter));
+        }
+
+        let list = List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::new().reversed());
+        let mut state = ListState::default().with_selected(Some(self.cursor));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_preview(&self, frame: &mut Frame, area: Rect) {
+        let text = self
+            .current()
+            .and_then(|index| self.previews.get(&index))
+            .map(String::as_str)
+            .unwrap_or_default();
+        let preview = Paragraph::new(text)
+            .block(Block::bordered().title(" Preview (PgUp/PgDn to scroll) "))
+            .scroll((self.preview_scroll, 0));
+        frame.render_widget(preview, area);
+    }
+}
@@ -18,49 +18,99 @@ impl CommandHandler for Update {
             _ => return Err(GinspError::Cli("Invalid subcommand".to_string()).into()),
         };
 
-        git::Git::validate_git_installed()?;
+        git::validate_ref_names(&update_cmd.branches)?;
 
-        git::Git::validate_git_repo()?;
-
-        if update_cmd.verbose {
-            println!("Fetching all branches.");
+        if cli.workspace.is_none() {
+            let git = git::open(cli.repo.as_deref())?;
+            return update_repository(git.as_ref(), update_cmd);
         }
-        git::Git::fetch_all()
-            .map(|std| {
-                if update_cmd.verbose {
-                    git::Git::print_stdout(std);
-                }
-            })
-            .map_err(|err| {
-                GinspError::Git(format!("Fail to fetch all branches. Error: {}", err))
-            })?;
 
-        for branch in update_cmd.branches.iter() {
+        // In workspace mode keep updating the other repositories when one fails,
+        // then print a combined report grouped by repository.
+        let mut results = vec![];
+        for repository in cli.repositories()? {
             if update_cmd.verbose {
-                println!("Checking out branch: {}", branch);
+                println!("\nRepository: {}", repository.name);
             }
-            git::Git::checkout_branch(branch)
-                .map(|std| {
-                    if update_cmd.verbose {
-                        git::Git::print_stdout(std);
-                    }
-                })
-                .map_err(|err| {
-                    GinspError::Git(format!("Fail to checkout branch. Error: {}", err))
-                })?;
+            let result = git::open(repository.path.as_deref())
+                .map_err(anyhow::Error::from)
+                .and_then(|git| update_repository(git.as_ref(), update_cmd));
+            results.push((repository.name, result));
+        }
 
-            if update_cmd.verbose {
-                println!("Pulling branch: {}", branch);
+        println!("\nUpdate result:");
+        println!("------------------------");
+        let width = results
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        let mut failed = 0;
+        for (name, result) in results.iter() {
+            match result {
+                Ok(_) => println!("  {:width$} updated", name, width = width),
+                Err(err) => {
+                    failed += 1;
+                    // indent the continuation lines of multi-line git errors
+                    let err = err.to_string().trim().replace('\n', "\n      ");
+                    println!("  {:width$} failed: {}", name, err, width = width);
+                }
             }
-            git::Git::pull_branch()
-                .map(|std| {
-                    if update_cmd.verbose {
-                        git::Git::print_stdout(std);
-                    }
-                })
-                .map_err(|err| GinspError::Git(format!("Fail to pull branch. Error: {}", err)))?;
+        }
+        println!();
+
+        if failed > 0 {
+            return Err(GinspError::Git(format!(
+                "Fail to update {} of {} repositories",
+                failed,
+                results.len()
+            ))
+            .into());
         }
 
         anyhow::Ok(())
     }
 }
+
+fn update_repository(git: &dyn git::GitBackend, update_cmd: &cli::Update) -> anyhow::Result<()> {
+    git.validate_git_installed()?;
+
+    git.validate_git_repo()?;
+
+    if update_cmd.verbose {
+        println!("Fetching all branches.");
+    }
+    git.fetch_all()
+        .map(|std| {
+            if update_cmd.verbose {
+                git::print_stdout(std);
+            }
+        })
+        .map_err(|err| GinspError::Git(format!("Fail to fetch all branches. Error: {}", err)))?;
+
+    for branch in update_cmd.branches.iter() {
+        if update_cmd.verbose {
+            println!("Checking out branch: {}", branch);
+        }
+        git.checkout_branch(branch)
+            .map(|std| {
+                if update_cmd.verbose {
+                    git::print_stdout(std);
+                }
+            })
+            .map_err(|err| GinspError::Git(format!("Fail to checkout branch. Error: {}", err)))?;
+
+        if update_cmd.verbose {
+            println!("Pulling branch: {}", branch);
+        }
+        git.pull_branch()
+            .map(|std| {
+                if update_cmd.verbose {
+                    git::print_stdout(std);
+                }
+            })
+            .map_err(|err| GinspError::Git(format!("Fail to pull branch. Error: {}", err)))?;
+    }
+
+    anyhow::Ok(())
+}
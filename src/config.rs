@@ -1,9 +1,42 @@
 use crate::error::{ConfigErrorKind, GinspError};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct Config {
     pub project_management: Option<ProjectManagement>,
+    #[serde(default)]
+    pub git: GitConfig,
+    #[serde(default)]
+    pub ignore: IgnoreConfig,
+}
+
+/// Noise commits left out of branch comparisons, see `ignore::IgnoreRules`.
+#[derive(Debug, Default, Deserialize)]
+pub struct IgnoreConfig {
+    #[serde(default)]
+    pub merges: bool,
+    #[serde(default)]
+    pub subject: Vec<String>,
+    #[serde(default)]
+    pub author: Vec<String>,
+    #[serde(default)]
+    pub trailer: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GitConfig {
+    #[serde(default)]
+    pub backend: GitBackendKind,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub enum GitBackendKind {
+    /// Run the `git` executable for every operation.
+    #[default]
+    Cli,
+    /// Read the repository in-process through libgit2.
+    Libgit2,
 }
 
 #[derive(Debug, Deserialize)]
@@ -12,16 +45,76 @@ pub struct ProjectManagement {
     pub url: String,
     pub credential_key: String,
     pub ticket_id_regex: String,
+    /// `owner/repo` of tickets referenced without a repository, e.g. `#123` on GitHub or GitLab.
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Organization of the tracker, e.g. the Linear URL key used in ticket links.
+    #[serde(default)]
+    pub organization: Option<String>,
+    /// Project of the tracker, e.g. the Azure DevOps project of the work items.
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Request and response mapping of the `Generic` provider.
+    #[serde(default)]
+    pub generic: Option<GenericTracker>,
+    /// Requests sent to the tracker at once, 8 when not set.
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// Seconds before a request to the tracker times out, 30 when not set.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// Seconds a fetched ticket is cached for, 3600 when not set.
+    #[serde(default)]
+    pub cache_ttl: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Any REST tracker: `url` is requested with `:ticket_id` replaced by the ticket id,
+/// and the ticket fields are read from the JSON response at the given JSON pointers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenericTracker {
+    #[serde(default)]
+    pub method: HttpMethod,
+    #[serde(default)]
+    pub auth_type: AuthType,
+    /// Extra request headers, `:credential_key` in a value is replaced by the credential key.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Request body, `:ticket_id` is replaced by the ticket id.
+    pub body: Option<String>,
+    /// JSON pointer of the status, e.g. `/fields/status/name`.
+    pub status: String,
+    pub title: Option<String>,
+    pub assignee: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    Post,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
 pub enum AuthType {
+    /// `credential_key` is `<username>:<password>`.
+    #[default]
     Basic,
+    /// `credential_key` is a bearer token.
+    Bearer,
+    /// No `Authorization` header, e.g. when the credential is sent in `headers`.
+    None,
 }
 
 #[derive(Debug, Deserialize)]
 pub enum ProjectManagementProvider {
     Jira,
+    GitHub,
+    GitLab,
+    Linear,
+    AzureDevOps,
+    Generic,
 }
 
 impl Config {
@@ -50,4 +143,32 @@ impl Config {
 
         Self::read_toml_file(file_path.as_str())
     }
+
+    /// Read the repo-local config file `<repo_root>/.ginsp/config.toml` if it exists,
+    /// otherwise fall back to the config file in the home directory.
+    pub fn read_config_file(repo_root: Option<&str>) -> anyhow::Result<Config, GinspError> {
+        if let Some(repo_root) = repo_root {
+            let file_path = format!("{}/.ginsp/config.toml", repo_root);
+            if std::path::Path::new(file_path.as_str()).is_file() {
+                return Self::read_toml_file(file_path.as_str());
+            }
+        }
+
+        Self::read_config_file_from_home_dir()
+    }
+
+    /// Same as `read_config_file` but returns the default config
+    /// when there is no config file at all.
+    pub fn read_config_file_or_default(
+        repo_root: Option<&str>,
+    ) -> anyhow::Result<Config, GinspError> {
+        match Self::read_config_file(repo_root) {
+            Err(GinspError::Config(ConfigErrorKind::IO(err)))
+                if err.kind() == std::io::ErrorKind::NotFound =>
+            {
+                Ok(Config::default())
+            }
+            result => result,
+        }
+    }
 }
@@ -9,7 +9,7 @@ pub enum GinspError {
     Config(ConfigErrorKind),
     #[error("Git error: {0}")]
     Git(String),
-    #[error("Jira error: {0}")]
+    #[error("Http error: {0}")]
     Http(String),
     #[error("System error: {0}")]
     System(String),
@@ -24,4 +24,10 @@ pub enum ConfigErrorKind {
     Syntax(#[from] toml::de::Error),
     #[error("Invalid credential key")]
     InvalidCredentialKey,
+    #[error("Invalid ignore rule {0}")]
+    InvalidIgnoreRule(String),
+    #[error("Invalid JSON pointer {0}, it must start with '/'")]
+    InvalidJsonPointer(String),
+    #[error("Invalid ticket id regex {0}")]
+    InvalidTicketIdRegex(String),
 }
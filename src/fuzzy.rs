@@ -0,0 +1,104 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// A commit subject reduced to what matters when comparing reworded commits,
+/// so that `[ABC-1] Fix null check` and `ABC-1: fix null check` are equal.
+/// Ticket ids are found by the `ticket_id_regex` of the config file when it is set,
+/// otherwise by a built-in pattern of ids like `ABC-1`.
+pub struct NormalizedSubject {
+    /// Ticket ids found in the subject, upper case.
+    pub tickets: Vec<String>,
+    /// Lower case words of the subject without ticket ids and punctuation.
+    pub words: String,
+}
+
+impl NormalizedSubject {
+    pub fn new(subject: &str, ticket_id_regex: Option<&Regex>) -> Self {
+        static TICKET: OnceLock<Regex> = OnceLock::new();
+        let ticket = ticket_id_regex.unwrap_or_else(|| {
+            TICKET.get_or_init(|| Regex::new(r"\b([A-Za-z][A-Za-z0-9]*-\d+)\b").unwrap())
+        });
+
+        // the ticket id is the first group, the whole match is left out of the words
+        let tickets = ticket
+            .captures_iter(subject)
+            .filter_map(|caps| caps.get(1))
+            .map(|id| id.as_str().to_uppercase())
+            .collect();
+
+        let words = ticket
+            .replace_all(subject, " ")
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() {
+                    c.to_lowercase().next().unwrap_or(c)
+                } else {
+                    ' '
+                }
+            })
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self { tickets, words }
+    }
+
+    /// Similarity between 0 and 1 of the two subjects.
+    /// Subjects that reference different tickets are never similar.
+    pub fn similarity(&self, other: &NormalizedSubject) -> f64 {
+        if !self.tickets.is_empty() && !other.tickets.is_empty() && self.tickets != other.tickets {
+            return 0.0;
+        }
+        strsim::normalized_levenshtein(&self.words, &other.words)
+    }
+}
+
+/// Parse the `--fuzzy` threshold, a number between 0 and 1.
+pub fn parse_threshold(value: &str) -> Result<f64, String> {
+    let threshold = value
+        .parse::<f64>()
+        .map_err(|_| format!("'{}' is not a number", value))?;
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(threshold)
+    } else {
+        Err("threshold must be between 0 and 1".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NormalizedSubject;
+    use regex::Regex;
+
+    fn similarity(a: &str, b: &str, ticket_id_regex: Option<&Regex>) -> f64 {
+        NormalizedSubject::new(a, ticket_id_regex)
+            .similarity(&NormalizedSubject::new(b, ticket_id_regex))
+    }
+
+    #[test]
+    fn same_ticket_with_another_prefix_matches() {
+        let similarity = similarity("[ABC-1] Fix null check", "ABC-1: fix null check", None);
+        assert_eq!(similarity, 1.0);
+    }
+
+    #[test]
+    fn different_tickets_never_match() {
+        let similarity = similarity("[ABC-1] Fix null check", "[ABC-2] Fix null check", None);
+        assert_eq!(similarity, 0.0);
+    }
+
+    #[test]
+    fn configured_ticket_regex_replaces_the_built_in_pattern() {
+        let ticket_id_regex = Regex::new(r"\[?(JIRA-\d+)\]?").unwrap();
+        let a = "[JIRA-7] Fix utf-8 decoding";
+        let b = "JIRA-7 fix utf-16 decoding";
+
+        assert_eq!(similarity(a, b, None), 0.0);
+        assert!(similarity(a, b, Some(&ticket_id_regex)) > 0.85);
+        assert_eq!(
+            similarity(a, "[JIRA-8] Fix utf-8 decoding", Some(&ticket_id_regex)),
+            0.0
+        );
+    }
+}
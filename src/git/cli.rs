@@ -0,0 +1,471 @@
+use crate::error::GinspError;
+use crate::git::{
+    Commit, FileStat, GitBackend, LogOptions, ProcessCommandStdout, Revision, Signature, Trailer,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// `git log` format of one commit, fields are separated by NUL
+/// and parsed by `parse_commit` in the same order.
+const LOG_FORMAT: &str = "--format=%H%x00%h%x00%P%x00%an%x00%ae%x00%aI%x00%cn%x00%ce%x00%cI%x00%s%x00%b%x00%(trailers:only,unfold)";
+const LOG_FORMAT_FIELDS: usize = 12;
+
+/// Backend that runs the `git` executable.
+pub struct CliBackend {
+    /// Repository to run git in, passed to git as `-C <path>`.
+    /// `None` runs git in the current working directory.
+    repo: Option<PathBuf>,
+}
+
+impl CliBackend {
+    pub fn new(repo: Option<&Path>) -> Self {
+        Self {
+            repo: repo.map(Path::to_path_buf),
+        }
+    }
+
+    /// Validate a branch name with `git check-ref-format --branch`.
+    /// Does not need a repository, so it can run before any other git call.
+    pub fn check_ref_format(name: &str) -> anyhow::Result<(), GinspError> {
+        let output = Command::new("git")
+            .args(["check-ref-format", "--branch", name])
+            .output()
+            .map_err(|err| GinspError::System(err.to_string()))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GinspError::Git(format!(
+                "Invalid branch name '{}': {}",
+                name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    fn run_git_command(&self, args: &[&str]) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let stdout = self.run_git_command_with_input(args, None)?;
+        Ok(String::from_utf8_lossy(&stdout).to_string())
+    }
+
+    /// Run git with the input written to its stdin, and return its raw stdout.
+    fn run_git_command_with_input(
+        &self,
+        args: &[&str],
+        input: Option<&[u8]>,
+    ) -> anyhow::Result<Vec<u8>, GinspError> {
+        let mut command = Command::new("git");
+        if let Some(repo) = &self.repo {
+            command.arg("-C").arg(repo);
+        }
+        command
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if input.is_some() {
+            command.stdin(Stdio::piped());
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|err| GinspError::System(err.to_string()))?;
+
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            stdin
+                .write_all(input)
+                .map_err(|err| GinspError::System(err.to_string()))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|err| GinspError::System(err.to_string()))?;
+
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(GinspError::Git(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+}
+
+impl GitBackend for CliBackend {
+    fn validate_git_installed(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["--version"])
+    }
+
+    fn validate_git_repo(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["status"])
+    }
+
+    fn fetch_all(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["fetch", "--all", "--prune", "--tags"])
+    }
+
+    fn fetch_refs(
+        &self,
+        remote: &str,
+        refspecs: &[String],
+    ) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let mut args = vec!["fetch", "--end-of-options", remote];
+        args.extend(refspecs.iter().map(String::as_str));
+        self.run_git_command(&args)
+    }
+
+    fn deepen(
+        &self,
+        remote: &str,
+        refspecs: &[String],
+        depth: Option<u32>,
+    ) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let deepen = match depth {
+            Some(depth) => format!("--deepen={}", depth),
+            None => "--unshallow".to_string(),
+        };
+        let mut args = vec!["fetch", deepen.as_str(), "--end-of-options", remote];
+        args.extend(refspecs.iter().map(String::as_str));
+        self.run_git_command(&args)
+    }
+
+    fn is_shallow(&self) -> anyhow::Result<bool, GinspError> {
+        let output = self.run_git_command(&["rev-parse", "--is-shallow-repository"])?;
+        Ok(output.trim() == "true")
+    }
+
+    fn checkout_branch(&self, branch: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["checkout", branch])
+    }
+
+    fn pull_branch(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["pull"])
+    }
+
+    fn get_current_branch(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let output = self.run_git_command(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+        Ok(output.trim().to_string())
+    }
+
+    fn get_toplevel(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let output = self.run_git_command(&["rev-parse", "--show-toplevel"])?;
+        Ok(output.trim().to_string())
+    }
+
+    fn rev_parse(&self, rev: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let rev = format!("{}^{{commit}}", rev);
+        let output = self.run_git_command(&["rev-parse", "--verify", rev.as_str()])?;
+        Ok(output.trim().to_string())
+    }
+
+    fn resolve_revision(&self, rev: &str) -> anyhow::Result<Revision, GinspError> {
+        let sha = self.rev_parse(rev)?;
+        // revisions are validated not to start with `-`, so `--verify` reads it as a revision
+        let full_name =
+            self.run_git_command(&["rev-parse", "--verify", "--symbolic-full-name", rev])?;
+        let full_name = Some(full_name.trim().to_string()).filter(|name| !name.is_empty());
+        Ok(Revision::new(rev, full_name, sha))
+    }
+
+    fn merge_base(&self, a: &str, b: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let output = self.run_git_command(&["merge-base", a, b])?;
+        Ok(output.trim().to_string())
+    }
+
+    fn changed_files(&self, from: &str, to: &str) -> anyhow::Result<Vec<String>, GinspError> {
+        let output = self.run_git_command(&[
+            "diff-tree",
+            "-r",
+            "--name-only",
+            "-z",
+            "--no-renames",
+            from,
+            to,
+        ])?;
+        Ok(output
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn diff_stat(&self, from: Option<&str>, to: &str) -> anyhow::Result<Vec<FileStat>, GinspError> {
+        let output = match from {
+            Some(from) => {
+                self.run_git_command(&["diff", "--numstat", "-z", "--no-renames", from, to, "--"])?
+            }
+            None => self.run_git_command(&[
+                "diff-tree",
+                "-r",
+                "--root",
+                "--no-commit-id",
+                "--numstat",
+                "-z",
+                "--no-renames",
+                to,
+            ])?,
+        };
+
+        // `<added>\t<removed>\t<path>` for each file, `-` counts for binary files
+        Ok(output
+            .split('\0')
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let added = fields.next()?;
+                let removed = fields.next()?;
+                let path = fields.next()?;
+                Some(FileStat {
+                    path: path.to_string(),
+                    added: added.parse().ok(),
+                    removed: removed.parse().ok(),
+                })
+            })
+            .collect())
+    }
+
+    fn patch_id(&self, from: &str, to: &str) -> anyhow::Result<Option<String>, GinspError> {
+        // plumbing, so that external diff drivers and colors of the user config stay out
+        let diff = self.run_git_command_with_input(
+            &[
+                "diff-tree",
+                "-r",
+                "-p",
+                "--no-ext-diff",
+                "--no-color",
+                "--no-renames",
+                from,
+                to,
+            ],
+            None,
+        )?;
+        if diff.is_empty() {
+            return Ok(None);
+        }
+        let output = self.run_git_command_with_input(&["patch-id", "--stable"], Some(&diff))?;
+        Ok(String::from_utf8_lossy(&output)
+            .split_whitespace()
+            .next()
+            .map(str::to_string))
+    }
+
+    fn file_blob(&self, rev: &str, path: &str) -> anyhow::Result<Option<String>, GinspError> {
+        let object = format!("{}:{}", rev, path);
+        match self.run_git_command(&["rev-parse", "--verify", "--quiet", object.as_str()]) {
+            Ok(output) => Ok(Some(output.trim().to_string())),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn log(&self, rev: &str, options: &LogOptions) -> anyhow::Result<Vec<Commit>, GinspError> {
+        let mut args = vec!["log".to_string(), "-z".to_string(), LOG_FORMAT.to_string()];
+        if let Some(author) = &options.author {
+            args.push(format!("--author={}", author));
+        }
+        if let Some(since) = &options.since {
+            args.push(format!("--since={}", since));
+        }
+        if let Some(until) = &options.until {
+            args.push(format!("--until={}", until));
+        }
+        // `--end-of-options` keeps a revision starting with `-` from being read as an option,
+        // `--` keeps it from being read as a path
+        args.push("--end-of-options".to_string());
+        args.push(rev.to_string());
+        args.push("--".to_string());
+        args.extend(options.paths.iter().cloned());
+
+        let output = self.run_git_command(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+        parse_log(&output, rev)
+    }
+
+    fn show(&self, sha: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["show", "--stat", "--patch", "--end-of-options", sha])
+    }
+
+    fn cherry_pick(&self, hash: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["cherry-pick", hash])
+    }
+
+    fn cherry_pick_abort(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["cherry-pick", "--abort"])
+    }
+
+    fn reset_hard(&self, hash: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.run_git_command(&["reset", "--hard", hash])
+    }
+}
+
+/// Parse the `git log -z` output of `LOG_FORMAT`.
+fn parse_log(output: &str, rev: &str) -> anyhow::Result<Vec<Commit>, GinspError> {
+    // `-z` terminates commits with NUL as well, so the output is a flat list of fields
+    let output = output.strip_suffix('\0').unwrap_or(output);
+    if output.is_empty() {
+        return Ok(vec![]);
+    }
+    let fields = output.split('\0').collect::<Vec<_>>();
+    if fields.len() % LOG_FORMAT_FIELDS != 0 {
+        return Err(GinspError::Git(format!(
+            "Fail to parse the log of '{}': unexpected number of fields",
+            rev
+        )));
+    }
+
+    fields
+        .chunks_exact(LOG_FORMAT_FIELDS)
+        .map(parse_commit)
+        .collect()
+}
+
+fn parse_commit(fields: &[&str]) -> anyhow::Result<Commit, GinspError> {
+    let parse_date = |date: &str| {
+        chrono::DateTime::parse_from_rfc3339(date).map_err(|err| {
+            GinspError::Git(format!("Fail to parse commit date '{}': {}", date, err))
+        })
+    };
+
+    Ok(Commit {
+        sha: fields[0].to_string(),
+        short_sha: fields[1].to_string(),
+        parents: fields[2].split_whitespace().map(str::to_string).collect(),
+        author: Signature {
+            name: fields[3].to_string(),
+            email: fields[4].to_string(),
+        },
+        author_date: parse_date(fields[5])?,
+        committer: Signature {
+            name: fields[6].to_string(),
+            email: fields[7].to_string(),
+        },
+        committer_date: parse_date(fields[8])?,
+        subject: fields[9].to_string(),
+        body: fields[10].trim_end().to_string(),
+        trailers: Trailer::parse_lines(fields[11]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_log, CliBackend};
+    use crate::git::{GitBackend, LogOptions};
+    use std::process::Command;
+
+    #[test]
+    fn parse_log_reads_every_field() {
+        let merge = [
+            "a".repeat(40),
+            "aaaaaaa".to_string(),
+            format!("{} {}", "b".repeat(40), "c".repeat(40)),
+            "Jane Doe".to_string(),
+            "jane@example.com".to_string(),
+            "2024-01-02T03:04:05+07:00".to_string(),
+            "John Roe".to_string(),
+            "john@example.com".to_string(),
+            "2024-01-03T00:00:00Z".to_string(),
+            "Merge branch 'fix' into main".to_string(),
+            "Fix the null check.\n\nSecond paragraph.\n\nSigned-off-by: Jane Doe <jane@example.com>\nRelease-Note: none\n".to_string(),
+            "Signed-off-by: Jane Doe <jane@example.com>\nRelease-Note: none\n".to_string(),
+        ];
+        let root = [
+            "b".repeat(40),
+            "bbbbbbb".to_string(),
+            String::new(),
+            "Jane Doe".to_string(),
+            "jane@example.com".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+            "Jane Doe".to_string(),
+            "jane@example.com".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+            "Initial commit".to_string(),
+            String::new(),
+            String::new(),
+        ];
+        let output = format!("{}\0{}\0", merge.join("\0"), root.join("\0"));
+
+        let commits = parse_log(&output, "main").unwrap();
+
+        assert_eq!(commits.len(), 2);
+        let merge = &commits[0];
+        assert_eq!(merge.sha, "a".repeat(40));
+        assert_eq!(merge.short_sha, "aaaaaaa");
+        assert_eq!(merge.parents, ["b".repeat(40), "c".repeat(40)]);
+        assert_eq!(merge.author.name, "Jane Doe");
+        assert_eq!(merge.author_date.to_rfc3339(), "2024-01-02T03:04:05+07:00");
+        assert_eq!(merge.committer.email, "john@example.com");
+        assert_eq!(merge.subject, "Merge branch 'fix' into main");
+        assert!(merge
+            .body
+            .starts_with("Fix the null check.\n\nSecond paragraph."));
+        assert!(merge.body.ends_with("Release-Note: none"));
+        let trailers = merge
+            .trailers
+            .iter()
+            .map(|trailer| (trailer.key.as_str(), trailer.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trailers,
+            [
+                ("Signed-off-by", "Jane Doe <jane@example.com>"),
+                ("Release-Note", "none")
+            ]
+        );
+
+        let root = &commits[1];
+        assert!(root.parents.is_empty());
+        assert_eq!(root.body, "");
+        assert!(root.trailers.is_empty());
+    }
+
+    #[test]
+    fn parse_log_rejects_missing_fields() {
+        assert!(parse_log("a\0b\0", "main").is_err());
+        assert!(parse_log("", "main").unwrap().is_empty());
+    }
+
+    #[test]
+    fn log_reads_non_ascii_subjects_and_long_abbreviations() {
+        let repo = std::env::temp_dir().join(format!("ginsp-log-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&repo);
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args([
+                    "-c",
+                    "user.name=Zoë Ångström",
+                    "-c",
+                    "user.email=zoe@example.com",
+                    "-c",
+                    "commit.gpgsign=false",
+                ])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["config", "core.abbrev", "16"]);
+        git(&[
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "Corrige la mise à jour 🚀 des données",
+            "-m",
+            "Trailer-Key: välue",
+        ]);
+
+        let commits = CliBackend::new(Some(&repo))
+            .log("HEAD", &LogOptions::default())
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&repo);
+
+        assert_eq!(commits.len(), 1);
+        let commit = &commits[0];
+        assert_eq!(commit.subject, "Corrige la mise à jour 🚀 des données");
+        assert_eq!(commit.author.name, "Zoë Ångström");
+        assert_eq!(commit.short_sha.len(), 16);
+        assert!(commit.sha.starts_with(&commit.short_sha));
+        assert_eq!(commit.trailers[0].value, "välue");
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+
+/// A commit as loaded by `GitBackend::log`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Commit {
+    /// Full hash.
+    pub sha: String,
+    /// Abbreviated hash, as long as git needs it to be unique in the repository.
+    pub short_sha: String,
+    /// Full hashes of the parents, more than one for merge commits.
+    pub parents: Vec<String>,
+    pub author: Signature,
+    pub author_date: DateTime<FixedOffset>,
+    pub committer: Signature,
+    pub committer_date: DateTime<FixedOffset>,
+    /// First paragraph of the commit message, joined into one line.
+    pub subject: String,
+    /// Commit message without the subject.
+    pub body: String,
+    /// `Key: value` lines at the end of the commit message, e.g. `Signed-off-by`.
+    pub trailers: Vec<Trailer>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Signature {
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
+}
+
+/// A file changed by a commit, with the number of lines added and removed.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStat {
+    pub path: String,
+    /// `None` for binary files, and when only the paths were asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<usize>,
+}
+
+impl Commit {
+    /// Hash of the commit reverted by this one, from the
+    /// `This reverts commit <sha>.` line that `git revert` writes into the body.
+    pub fn reverted_sha(&self) -> Option<&str> {
+        let (_, rest) = self.body.split_once("This reverts commit ")?;
+        let end = rest
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(rest.len());
+        // shorter hex words are more likely plain text than a hash
+        (end >= 7).then(|| &rest[..end])
+    }
+
+    /// Subject of the commit reverted by this one, from a `Revert "<subject>"` subject.
+    pub fn reverted_subject(&self) -> Option<&str> {
+        self.subject
+            .strip_prefix("Revert \"")
+            .and_then(|subject| subject.strip_suffix('"'))
+    }
+}
+
+impl Trailer {
+    /// Parse the output of `%(trailers:only,unfold)`, one `Key: value` per line.
+    pub fn parse_lines(lines: &str) -> Vec<Trailer> {
+        lines
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| Trailer {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl Commit {
+    /// A commit with the given hash and message, and placeholder metadata.
+    pub fn with_message(sha: &str, subject: &str, body: &str) -> Self {
+        let signature = Signature {
+            name: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+        };
+        let date = DateTime::parse_from_rfc3339("2024-01-02T03:04:05+00:00").unwrap();
+        Self {
+            sha: sha.to_string(),
+            short_sha: sha[..7.min(sha.len())].to_string(),
+            parents: vec![],
+            author: signature.clone(),
+            author_date: date,
+            committer: signature,
+            committer_date: date,
+            subject: subject.to_string(),
+            body: body.to_string(),
+            trailers: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Commit;
+
+    #[test]
+    fn reverted_sha_reads_the_git_revert_line() {
+        let commit = Commit::with_message(
+            "b2",
+            "Revert \"Fix null check\"",
+            "This reverts commit 0a1b2c3d4e5f.\n\nBroke the build.",
+        );
+        assert_eq!(commit.reverted_sha(), Some("0a1b2c3d4e5f"));
+    }
+
+    #[test]
+    fn reverted_sha_ignores_short_hex_words() {
+        let commit = Commit::with_message("b2", "Fix revert", "This reverts commit abc.");
+        assert_eq!(commit.reverted_sha(), None);
+
+        let commit = Commit::with_message("b2", "Fix null check", "");
+        assert_eq!(commit.reverted_sha(), None);
+    }
+}
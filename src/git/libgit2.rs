@@ -0,0 +1,282 @@
+use crate::error::GinspError;
+use crate::git::{
+    CliBackend, Commit, FileStat, GitBackend, LogOptions, ProcessCommandStdout, Revision,
+    Signature, Trailer,
+};
+use chrono::{DateTime, FixedOffset};
+use std::path::Path;
+
+/// Backend that reads the repository in-process through libgit2.
+///
+/// Reading operations (log, rev-parse, merge-base, ...) never spawn a process.
+/// Operations that change the working tree or talk to a remote are delegated
+/// to the `git` executable, so that hooks, credentials, and signing behave
+/// exactly like with `CliBackend`.
+pub struct Libgit2Backend {
+    repo: git2::Repository,
+    cli: CliBackend,
+}
+
+impl Libgit2Backend {
+    pub fn open(path: Option<&Path>, cli: CliBackend) -> anyhow::Result<Self, GinspError> {
+        let repo = git2::Repository::discover(path.unwrap_or(Path::new("."))).map_err(to_error)?;
+        Ok(Self { repo, cli })
+    }
+
+    fn find_commit(&self, rev: &str) -> anyhow::Result<git2::Commit<'_>, GinspError> {
+        self.repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(to_error)
+    }
+
+    fn short_id(&self, commit: &git2::Commit<'_>) -> anyhow::Result<String, GinspError> {
+        let short_id = commit.as_object().short_id().map_err(to_error)?;
+        Ok(short_id.as_str().unwrap_or_default().to_string())
+    }
+
+    fn diff(&self, from: Option<&str>, to: &str) -> anyhow::Result<git2::Diff<'_>, GinspError> {
+        let from = match from {
+            Some(from) => Some(self.find_commit(from)?.tree().map_err(to_error)?),
+            None => None,
+        };
+        let to = self.find_commit(to)?.tree().map_err(to_error)?;
+        self.repo
+            .diff_tree_to_tree(from.as_ref(), Some(&to), None)
+            .map_err(to_error)
+    }
+
+    fn to_commit(&self, commit: &git2::Commit<'_>) -> anyhow::Result<Commit, GinspError> {
+        let message = String::from_utf8_lossy(commit.message_bytes()).to_string();
+        let trailers = git2::message_trailers_strs(message.as_str())
+            .map(|trailers| {
+                trailers
+                    .iter()
+                    .map(|(key, value)| Trailer {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Commit {
+            sha: commit.id().to_string(),
+            short_sha: self.short_id(commit)?,
+            parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+            author: to_signature(&commit.author()),
+            author_date: to_date(commit.author().when())?,
+            committer: to_signature(&commit.committer()),
+            committer_date: to_date(commit.committer().when())?,
+            subject: String::from_utf8_lossy(commit.summary_bytes().unwrap_or_default())
+                .to_string(),
+            body: String::from_utf8_lossy(commit.body_bytes().unwrap_or_default())
+                .trim_end()
+                .to_string(),
+            trailers,
+        })
+    }
+}
+
+impl GitBackend for Libgit2Backend {
+    fn validate_git_installed(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        // fetching and picking still need the git executable
+        self.cli.validate_git_installed()
+    }
+
+    fn validate_git_repo(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        // the repository was already opened in `Libgit2Backend::open`
+        Ok(String::new())
+    }
+
+    fn fetch_all(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.fetch_all()
+    }
+
+    fn fetch_refs(
+        &self,
+        remote: &str,
+        refspecs: &[String],
+    ) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.fetch_refs(remote, refspecs)
+    }
+
+    fn deepen(
+        &self,
+        remote: &str,
+        refspecs: &[String],
+        depth: Option<u32>,
+    ) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.deepen(remote, refspecs, depth)
+    }
+
+    fn is_shallow(&self) -> anyhow::Result<bool, GinspError> {
+        Ok(self.repo.is_shallow())
+    }
+
+    fn checkout_branch(&self, branch: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.checkout_branch(branch)
+    }
+
+    fn pull_branch(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.pull_branch()
+    }
+
+    fn get_current_branch(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        if self.repo.head_detached().map_err(to_error)? {
+            return Ok("HEAD".to_string());
+        }
+        let head = self.repo.head().map_err(to_error)?;
+        Ok(head.shorthand().unwrap_or("HEAD").to_string())
+    }
+
+    fn get_toplevel(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let workdir = self.repo.workdir().ok_or(GinspError::Git(
+            "Repository has no working directory".to_string(),
+        ))?;
+        Ok(workdir.to_string_lossy().trim_end_matches('/').to_string())
+    }
+
+    fn rev_parse(&self, rev: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        Ok(self.find_commit(rev)?.id().to_string())
+    }
+
+    fn resolve_revision(&self, rev: &str) -> anyhow::Result<Revision, GinspError> {
+        let (object, reference) = self.repo.revparse_ext(rev).map_err(to_error)?;
+        let sha = object.peel_to_commit().map_err(to_error)?.id().to_string();
+        // `HEAD` resolves to the branch it points to
+        let full_name = reference
+            .and_then(|reference| reference.resolve().ok())
+            .and_then(|reference| reference.name().map(str::to_string));
+        Ok(Revision::new(rev, full_name, sha))
+    }
+
+    fn merge_base(&self, a: &str, b: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        let a = self.find_commit(a)?.id();
+        let b = self.find_commit(b)?.id();
+        let base = self.repo.merge_base(a, b).map_err(to_error)?;
+        Ok(base.to_string())
+    }
+
+    fn changed_files(&self, from: &str, to: &str) -> anyhow::Result<Vec<String>, GinspError> {
+        let diff = self.diff(Some(from), to)?;
+        Ok(diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or(delta.old_file().path()))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect())
+    }
+
+    fn diff_stat(&self, from: Option<&str>, to: &str) -> anyhow::Result<Vec<FileStat>, GinspError> {
+        let diff = self.diff(from, to)?;
+        let mut stats = vec![];
+        for index in 0..diff.deltas().len() {
+            let Some(patch) = git2::Patch::from_diff(&diff, index).map_err(to_error)? else {
+                continue;
+            };
+            let delta = patch.delta();
+            let path = delta
+                .new_file()
+                .path()
+                .or(delta.old_file().path())
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let (added, removed) = if delta.flags().is_binary() {
+                (None, None)
+            } else {
+                let (_, added, removed) = patch.line_stats().map_err(to_error)?;
+                (Some(added), Some(removed))
+            };
+            stats.push(FileStat {
+                path,
+                added,
+                removed,
+            });
+        }
+        Ok(stats)
+    }
+
+    fn patch_id(&self, from: &str, to: &str) -> anyhow::Result<Option<String>, GinspError> {
+        let diff = self.diff(Some(from), to)?;
+        if diff.deltas().len() == 0 {
+            return Ok(None);
+        }
+        Ok(Some(diff.patchid(None).map_err(to_error)?.to_string()))
+    }
+
+    fn file_blob(&self, rev: &str, path: &str) -> anyhow::Result<Option<String>, GinspError> {
+        let tree = self.find_commit(rev)?.tree().map_err(to_error)?;
+        Ok(tree
+            .get_path(Path::new(path))
+            .ok()
+            .map(|entry| entry.id().to_string()))
+    }
+
+    fn log(&self, rev: &str, options: &LogOptions) -> anyhow::Result<Vec<Commit>, GinspError> {
+        // author patterns, approximate dates, and pathspecs (with history simplification)
+        // are left to git itself so that filtered logs match `git log` exactly
+        if !options.is_empty() {
+            return self.cli.log(rev, options);
+        }
+
+        let start = self.find_commit(rev)?.id();
+
+        let mut revwalk = self.repo.revwalk().map_err(to_error)?;
+        // children before parents like `git log`, even when commit times are equal
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+            .map_err(to_error)?;
+        revwalk.push(start).map_err(to_error)?;
+
+        let mut result = vec![];
+        for oid in revwalk {
+            let commit = self
+                .repo
+                .find_commit(oid.map_err(to_error)?)
+                .map_err(to_error)?;
+            result.push(self.to_commit(&commit)?);
+        }
+
+        Ok(result)
+    }
+
+    fn show(&self, sha: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        // the patch text is rendered by git itself
+        self.cli.show(sha)
+    }
+
+    fn cherry_pick(&self, hash: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.cherry_pick(hash)
+    }
+
+    fn cherry_pick_abort(&self) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.cherry_pick_abort()
+    }
+
+    fn reset_hard(&self, hash: &str) -> anyhow::Result<ProcessCommandStdout, GinspError> {
+        self.cli.reset_hard(hash)
+    }
+}
+
+fn to_error(err: git2::Error) -> GinspError {
+    GinspError::Git(err.message().to_string())
+}
+
+fn to_signature(signature: &git2::Signature<'_>) -> Signature {
+    Signature {
+        name: String::from_utf8_lossy(signature.name_bytes()).to_string(),
+        email: String::from_utf8_lossy(signature.email_bytes()).to_string(),
+    }
+}
+
+fn to_date(time: git2::Time) -> anyhow::Result<DateTime<FixedOffset>, GinspError> {
+    FixedOffset::east_opt(time.offset_minutes() * 60)
+        .and_then(|offset| {
+            DateTime::from_timestamp(time.seconds(), 0).map(|date| date.with_timezone(&offset))
+        })
+        .ok_or(GinspError::Git(format!(
+            "Invalid commit time {} {}",
+            time.seconds(),
+            time.offset_minutes()
+        )))
+}
@@ -0,0 +1,164 @@
+mod cli;
+mod commit;
+mod libgit2;
+mod revision;
+
+pub use cli::CliBackend;
+pub use commit::{Commit, FileStat, Signature, Trailer};
+pub use libgit2::Libgit2Backend;
+pub use revision::Revision;
+
+use crate::config::{Config, GitBackendKind};
+use crate::error::GinspError;
+use std::path::Path;
+
+type ProcessCommandStdout = String;
+
+/// Filters applied by `GitBackend::log`, with the same meaning as the `git log` options.
+#[derive(Debug, Default)]
+pub struct LogOptions {
+    /// `--author=<pattern>`
+    pub author: Option<String>,
+    /// `--since=<date>`
+    pub since: Option<String>,
+    /// `--until=<date>`
+    pub until: Option<String>,
+    /// `-- <pathspec>...`
+    pub paths: Vec<String>,
+}
+
+impl LogOptions {
+    pub fn is_empty(&self) -> bool {
+        self.author.is_none()
+            && self.since.is_none()
+            && self.until.is_none()
+            && self.paths.is_empty()
+    }
+}
+
+/// Git operations used by ginsp.
+///
+/// `CliBackend` runs the `git` executable for every operation, `Libgit2Backend`
+/// reads the repository in-process and is selected with `[git] backend = "Libgit2"`.
+pub trait GitBackend {
+    fn validate_git_installed(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn validate_git_repo(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn fetch_all(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    /// Fetch only the given refspecs from the remote.
+    fn fetch_refs(
+        &self,
+        remote: &str,
+        refspecs: &[String],
+    ) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    /// Fetch `depth` more commits of history for the refspecs of a shallow clone,
+    /// or the whole history when `depth` is `None`.
+    fn deepen(
+        &self,
+        remote: &str,
+        refspecs: &[String],
+        depth: Option<u32>,
+    ) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn is_shallow(&self) -> anyhow::Result<bool, GinspError>;
+
+    fn checkout_branch(&self, branch: &str) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn pull_branch(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn get_current_branch(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    /// Absolute path of the top level directory of the working tree.
+    fn get_toplevel(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    /// Full hash of the commit the revision points to.
+    fn rev_parse(&self, rev: &str) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    /// Resolve the revision and find out whether it is a branch, a remote branch, a tag, or a sha.
+    fn resolve_revision(&self, rev: &str) -> anyhow::Result<Revision, GinspError>;
+
+    /// Full hash of the best common ancestor of the two revisions.
+    fn merge_base(&self, a: &str, b: &str) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    /// Paths changed between the two commits.
+    fn changed_files(&self, from: &str, to: &str) -> anyhow::Result<Vec<String>, GinspError>;
+
+    /// Files changed between the two commits with their added and removed lines,
+    /// from the empty tree when there is no `from` commit (root commits).
+    fn diff_stat(&self, from: Option<&str>, to: &str) -> anyhow::Result<Vec<FileStat>, GinspError>;
+
+    /// Patch id of the diff between the two commits, `None` when they have the same tree.
+    /// Equal patch ids mean the same change, whatever the commits it was applied to.
+    fn patch_id(&self, from: &str, to: &str) -> anyhow::Result<Option<String>, GinspError>;
+
+    /// Hash of the file at the path in the commit, `None` when there is no such file.
+    fn file_blob(&self, rev: &str, path: &str) -> anyhow::Result<Option<String>, GinspError>;
+
+    /// Commits reachable from the revision that match the filters, newest first.
+    fn log(&self, rev: &str, options: &LogOptions) -> anyhow::Result<Vec<Commit>, GinspError>;
+
+    /// Message, diffstat, and patch of the commit as printed by `git show`.
+    fn show(&self, sha: &str) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn cherry_pick(&self, hash: &str) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn cherry_pick_abort(&self) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+
+    fn reset_hard(&self, hash: &str) -> anyhow::Result<ProcessCommandStdout, GinspError>;
+}
+
+/// Open the repository with the backend selected in the config file,
+/// `CliBackend` when there is no config file.
+pub fn open(repo: Option<&Path>) -> anyhow::Result<Box<dyn GitBackend>, GinspError> {
+    let cli = CliBackend::new(repo);
+    let repo_root = cli.get_toplevel().ok();
+    let config = Config::read_config_file_or_default(repo_root.as_deref())?;
+
+    match config.git.backend {
+        GitBackendKind::Cli => Ok(Box::new(cli)),
+        GitBackendKind::Libgit2 => Ok(Box::new(Libgit2Backend::open(repo, cli)?)),
+    }
+}
+
+/// Validate branch names given on the command line before they reach any other git call.
+pub fn validate_ref_names(names: &[String]) -> anyhow::Result<(), GinspError> {
+    for name in names.iter() {
+        CliBackend::check_ref_format(name)?;
+    }
+    Ok(())
+}
+
+/// Validate revisions given on the command line before they reach any other git call.
+/// A revision is a ref name or a sha, optionally followed by `~`, `^`, or `@{...}` suffixes,
+/// and only the ref name part is checked with `git check-ref-format`.
+pub fn validate_revisions(revisions: &[String]) -> anyhow::Result<(), GinspError> {
+    for revision in revisions.iter() {
+        let end = revision
+            .find(['~', '^'])
+            .into_iter()
+            .chain(revision.find("@{"))
+            .min()
+            .unwrap_or(revision.len());
+        let name = &revision[..end];
+
+        // `HEAD~2`, `@{-1}`, ... refer to the current branch
+        if name.is_empty() || name == "HEAD" || name == "@" {
+            continue;
+        }
+
+        CliBackend::check_ref_format(name)
+            .map_err(|_| GinspError::Git(format!("Invalid revision '{}'", revision)))?;
+    }
+    Ok(())
+}
+
+pub fn print_stdout(stdout: ProcessCommandStdout) {
+    println!("{}", stdout);
+}
+
+pub fn print_stderr(stderr: ProcessCommandStdout) {
+    eprintln!("{}", stderr);
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::fmt;
+
+/// A revision given on the command line, resolved to a commit.
+#[derive(Debug, Clone, Serialize)]
+pub struct Revision {
+    /// The revision as given, e.g. `main`, `v1.22.0`, or `HEAD~50`.
+    pub name: String,
+    pub kind: RevisionKind,
+    /// Full name of the ref the revision points to, e.g. `refs/heads/main`.
+    /// `None` for revisions that are not a ref, e.g. `HEAD~50`.
+    pub full_name: Option<String>,
+    /// Full hash of the commit.
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionKind {
+    Branch,
+    RemoteBranch,
+    Tag,
+    Sha,
+}
+
+impl Revision {
+    pub fn new(name: &str, full_name: Option<String>, sha: String) -> Self {
+        let full_name = full_name.filter(|full_name| full_name.starts_with("refs/"));
+        let kind = match full_name.as_deref() {
+            Some(full_name) if full_name.starts_with("refs/heads/") => RevisionKind::Branch,
+            Some(full_name) if full_name.starts_with("refs/remotes/") => RevisionKind::RemoteBranch,
+            Some(full_name) if full_name.starts_with("refs/tags/") => RevisionKind::Tag,
+            _ => RevisionKind::Sha,
+        };
+
+        Self {
+            name: name.to_string(),
+            kind,
+            full_name,
+            sha,
+        }
+    }
+
+    /// Name of the local branch the revision points to, if it is one.
+    pub fn local_branch(&self) -> Option<&str> {
+        self.full_name
+            .as_deref()
+            .and_then(|full_name| full_name.strip_prefix("refs/heads/"))
+    }
+}
+
+impl fmt::Display for RevisionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            RevisionKind::Branch => "branch",
+            RevisionKind::RemoteBranch => "remote branch",
+            RevisionKind::Tag => "tag",
+            RevisionKind::Sha => "sha",
+        };
+        write!(f, "{}", kind)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::config::IgnoreConfig;
+use crate::error::{ConfigErrorKind, GinspError};
+use crate::git::Commit;
+use regex::Regex;
+
+pub const IGNORE_FILE: &str = ".ginspignore";
+
+/// Rules for noise commits that are left out of branch comparisons,
+/// e.g. merge commits and version bumps.
+///
+/// Rules come from `[ignore]` in the config file and from the repo-local
+/// `.ginspignore` file, one rule per line:
+///
+/// ```text
+/// # comment
+/// merges
+/// subject:^Merge branch '.*' into
+/// author:^dependabot
+/// trailer:^Release-Note: none$
+/// ^Bump version      <- a line without prefix is a subject rule
+/// ```
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    /// Ignore commits with more than one parent.
+    pub merges: bool,
+    /// Matched against the subject.
+    pub subject: Vec<Regex>,
+    /// Matched against `Name <email>` of the author.
+    pub author: Vec<Regex>,
+    /// Matched against each `Key: value` trailer.
+    pub trailer: Vec<Regex>,
+}
+
+impl IgnoreRules {
+    pub fn from_config(config: &IgnoreConfig) -> anyhow::Result<IgnoreRules, GinspError> {
+        Ok(IgnoreRules {
+            merges: config.merges,
+            subject: compile(&config.subject)?,
+            author: compile(&config.author)?,
+            trailer: compile(&config.trailer)?,
+        })
+    }
+
+    /// Add the rules of the `.ginspignore` file in the directory, if it exists.
+    pub fn read_ignore_file(&mut self, dir: &str) -> anyhow::Result<(), GinspError> {
+        let path = std::path::Path::new(dir).join(IGNORE_FILE);
+        if !path.is_file() {
+            return Ok(());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| GinspError::Config(ConfigErrorKind::IO(err)))?;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line == "merges" {
+                self.merges = true;
+                continue;
+            }
+
+            let (rules, pattern) = match line.split_once(':') {
+                Some(("subject", pattern)) => (&mut self.subject, pattern),
+                Some(("author", pattern)) => (&mut self.author, pattern),
+                Some(("trailer", pattern)) => (&mut self.trailer, pattern),
+                _ => (&mut self.subject, line),
+            };
+            rules.push(compile_one(pattern.trim())?);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.merges && self.subject.is_empty() && self.author.is_empty() && self.trailer.is_empty()
+    }
+
+    pub fn is_ignored(&self, commit: &Commit) -> bool {
+        if self.merges && commit.parents.len() > 1 {
+            return true;
+        }
+
+        if self.subject.iter().any(|re| re.is_match(&commit.subject)) {
+            return true;
+        }
+
+        let author = format!("{} <{}>", commit.author.name, commit.author.email);
+        if self.author.iter().any(|re| re.is_match(&author)) {
+            return true;
+        }
+
+        commit.trailers.iter().any(|trailer| {
+            let trailer = format!("{}: {}", trailer.key, trailer.value);
+            self.trailer.iter().any(|re| re.is_match(&trailer))
+        })
+    }
+}
+
+fn compile(patterns: &[String]) -> anyhow::Result<Vec<Regex>, GinspError> {
+    patterns
+        .iter()
+        .map(|pattern| compile_one(pattern))
+        .collect()
+}
+
+fn compile_one(pattern: &str) -> anyhow::Result<Regex, GinspError> {
+    Regex::new(pattern).map_err(|err| {
+        GinspError::Config(ConfigErrorKind::InvalidIgnoreRule(format!(
+            "'{}': {}",
+            pattern, err
+        )))
+    })
+}
@@ -1,8 +1,11 @@
 mod cli;
 mod config;
 mod error;
+mod fuzzy;
 mod git;
-mod jira;
+mod ignore;
+mod tracker;
+mod workspace;
 
 use anyhow::{Ok, Result};
 
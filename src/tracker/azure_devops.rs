@@ -0,0 +1,222 @@
+use crate::error::GinspError;
+use crate::tracker::{copy_error, send_json, Http, IssueTracker, Ticket};
+use std::collections::HashMap;
+
+/// Work items fetched in one request, the most the API allows.
+const BATCH_SIZE: usize = 200;
+
+const API_VERSION: &str = "7.0";
+
+const FIELDS: &str = "System.State,System.WorkItemType,System.IterationPath";
+
+/// Azure DevOps (Azure Boards) work items API, `url` is the server,
+/// e.g. `https://dev.azure.com`. Tickets are `AB#1234` or plain work item ids.
+pub struct AzureDevOps {
+    url: String,
+    token: String,
+    organization: String,
+    project: String,
+    http: Http,
+}
+
+impl AzureDevOps {
+    pub fn new(
+        url: String,
+        token: String,
+        organization: String,
+        project: String,
+        http: Http,
+    ) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            organization,
+            project,
+            http,
+        }
+    }
+
+    fn project_url(&self) -> String {
+        format!("{}/{}/{}", self.url, self.organization, self.project)
+    }
+
+    fn get_json(&self, url: &str) -> Result<serde_json::Value, GinspError> {
+        // personal access tokens are sent as the password of an empty user
+        send_json(
+            self.http
+                .client()
+                .get(url)
+                .header("Accept", "application/json")
+                .basic_auth("", Some(&self.token)),
+        )
+    }
+
+    /// Fetch the work items in one request, by their numeric ids.
+    fn fetch_batch(&self, ids: &[&str]) -> Result<HashMap<String, serde_json::Value>, GinspError> {
+        // unknown work items are returned as `null` with `errorPolicy=omit`
+        let json = self.get_json(&format!(
+            "{}/_apis/wit/workitems?ids={}&fields={}&errorPolicy=omit&api-version={}",
+            self.project_url(),
+            ids.join(","),
+            FIELDS,
+            API_VERSION
+        ))?;
+
+        Ok(json["value"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|work_item| {
+                let id = work_item["id"].as_u64()?;
+                Some((id.to_string(), work_item["fields"].clone()))
+            })
+            .collect())
+    }
+}
+
+/// Numeric id of an `AB#1234` ticket.
+fn work_item_id(id: &str) -> Result<&str, GinspError> {
+    let number = id.rsplit('#').next().unwrap_or(id);
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(GinspError::Cli(format!("Invalid work item {}", id)));
+    }
+    Ok(number)
+}
+
+/// State, type, and iteration of a work item, e.g. `Active (Bug, Sprint 12)`.
+fn to_ticket(fields: Option<&serde_json::Value>) -> Result<Ticket, GinspError> {
+    let fields = fields.ok_or(GinspError::Http("Error: work item not found".to_string()))?;
+    let state = fields["System.State"]
+        .as_str()
+        .ok_or(GinspError::Http("Error: state not found".to_string()))?;
+
+    let kind = fields["System.WorkItemType"].as_str();
+
+    let details = [
+        kind,
+        // only the last part of `Project\Release 1\Sprint 12`
+        fields["System.IterationPath"]
+            .as_str()
+            .and_then(|path| path.rsplit('\\').next()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    let mut status = state.to_string();
+    if !details.is_empty() {
+        status.push_str(&format!(" ({})", details.join(", ")));
+    }
+
+    Ok(Ticket {
+        kind: kind.map(str::to_string),
+        ..Ticket::with_status(status)
+    })
+}
+
+impl IssueTracker for AzureDevOps {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        let number = work_item_id(id)?;
+        let work_items = self.fetch_batch(&[number])?;
+        to_ticket(work_items.get(number))
+    }
+
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        let mut tickets = HashMap::new();
+
+        let mut numbers = vec![];
+        for id in ids {
+            match work_item_id(id) {
+                Ok(number) => numbers.push((id, number)),
+                Err(err) => {
+                    tickets.insert(id.to_string(), Err(err));
+                }
+            }
+        }
+
+        let chunks = numbers.chunks(BATCH_SIZE).collect::<Vec<_>>();
+        let batches = self.http.for_each(&chunks, |chunk| {
+            let batch = chunk.iter().map(|(_, number)| *number).collect::<Vec<_>>();
+            self.fetch_batch(&batch)
+        });
+
+        for (chunk, batch) in chunks.into_iter().zip(batches) {
+            match batch {
+                Ok(work_items) => {
+                    for (id, number) in chunk {
+                        tickets.insert(id.to_string(), to_ticket(work_items.get(*number)));
+                    }
+                }
+                Err(err) => {
+                    for (id, _) in chunk {
+                        tickets.insert(id.to_string(), Err(copy_error(&err)));
+                    }
+                }
+            }
+        }
+
+        tickets
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        format!(
+            "{}/_workitems/edit/{}",
+            self.project_url(),
+            work_item_id(id).unwrap_or(id)
+        )
+    }
+
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        self.get_json(&format!(
+            "{}/{}/_apis/projects/{}?api-version={}",
+            self.url, self.organization, self.project, API_VERSION
+        ))
+        .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracker::mock::{open_tracker, MockServer};
+
+    #[test]
+    fn fetch_tickets_in_one_request() {
+        let server = MockServer::start(|request| match request.path() {
+            "/acme/Web%20App/_apis/wit/workitems" => (
+                200,
+                r#"{"value": [
+                    {"id": 1234, "fields": {"System.State": "Active", "System.WorkItemType": "Bug",
+                        "System.IterationPath": "Web App\\Release 1\\Sprint 12"}},
+                    null
+                ]}"#
+                .to_string(),
+            ),
+            _ => (404, "{}".to_string()),
+        });
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "AzureDevOps"
+            url = "{}"
+            credential_key = "pat"
+            organization = "acme"
+            project = "Web App"
+            ticket_id_regex = '(AB#\d+)'
+            "#,
+            server.url
+        ));
+
+        let ids = ["AB#1234", "AB#404", "AB#x"].map(str::to_string);
+        let tickets = tracker.fetch_tickets(&ids);
+        let ticket = tickets["AB#1234"].as_ref().unwrap();
+        assert_eq!(ticket.status, "Active (Bug, Sprint 12)");
+        assert_eq!(ticket.kind.as_deref(), Some("Bug"));
+        assert!(tickets["AB#404"].is_err());
+        assert!(tickets["AB#x"].is_err());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].query().starts_with("ids=1234,404&"));
+        // `:pat` in base64
+        assert_eq!(requests[0].header("authorization"), Some("Basic OnBhdA=="));
+    }
+}
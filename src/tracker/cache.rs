@@ -0,0 +1,296 @@
+use crate::config::ProjectManagement;
+use crate::error::GinspError;
+use crate::tracker::{IssueTracker, Ticket};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// How the ticket cache is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CacheMode {
+    /// Use tickets cached within the TTL, fetch the others.
+    Default,
+    /// Fetch every ticket, `--refresh-tickets`.
+    Refresh,
+    /// Use only cached tickets, `--offline`.
+    Offline,
+}
+
+impl CacheMode {
+    pub fn new(is_refresh: bool, is_offline: bool) -> Self {
+        if is_offline {
+            CacheMode::Offline
+        } else if is_refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Default
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at: DateTime<Utc>,
+    ticket: Ticket,
+}
+
+/// Tickets of a tracker cached in `~/.ginsp/cache/`, one file per tracker.
+pub struct Cached {
+    tracker: Box<dyn IssueTracker>,
+    path: Option<PathBuf>,
+    ttl: Duration,
+    mode: CacheMode,
+}
+
+impl Cached {
+    /// Cache the tickets of `tracker` in the file at `path`, with no cache without `path`.
+    pub fn new(
+        tracker: Box<dyn IssueTracker>,
+        path: Option<PathBuf>,
+        ttl: u64,
+        mode: CacheMode,
+    ) -> Self {
+        Self {
+            tracker,
+            path,
+            ttl: Duration::seconds(ttl.try_into().unwrap_or(i64::MAX)),
+            mode,
+        }
+    }
+
+    /// Path of the cache file of a tracker in `~/.ginsp/cache/`.
+    /// The repository, organization, and project are part of the file name,
+    /// since they resolve ids like `#12` to different tickets.
+    pub fn default_path(project_management: &ProjectManagement) -> Option<PathBuf> {
+        let scope = [
+            &project_management.repository,
+            &project_management.organization,
+            &project_management.project,
+        ]
+        .into_iter()
+        .flatten()
+        .fold(String::new(), |scope, part| scope + "-" + part);
+
+        let file_name = format!(
+            "{:?}-{}{}.json",
+            project_management.provider, project_management.url, scope
+        )
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+        home::home_dir().map(|home| home.join(".ginsp").join("cache").join(file_name))
+    }
+
+    /// A missing or unreadable cache file is an empty cache.
+    fn load(&self) -> HashMap<String, CacheEntry> {
+        self.path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// The cache only saves requests, so failing to write it is not an error.
+    fn save(&self, entries: &HashMap<String, CacheEntry>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let Ok(json) = serde_json::to_string(entries) else {
+            return;
+        };
+        // written aside then renamed, so that concurrent runs never read a partial file
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        let is_written = path
+            .parent()
+            .is_some_and(|dir| std::fs::create_dir_all(dir).is_ok())
+            && std::fs::write(&temp_path, json).is_ok();
+        if !is_written || std::fs::rename(&temp_path, path).is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+    }
+}
+
+impl IssueTracker for Cached {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        self.fetch_tickets(&[id.to_string()])
+            .remove(id)
+            .unwrap_or(Err(GinspError::System(format!(
+                "Ticket {} not fetched",
+                id
+            ))))
+    }
+
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        let mut entries = self.load();
+        let now: DateTime<Utc> = std::time::SystemTime::now().into();
+        let is_fresh = |entry: &CacheEntry| now - entry.fetched_at < self.ttl;
+
+        if self.mode == CacheMode::Offline {
+            return ids
+                .iter()
+                .map(|id| {
+                    let ticket = match entries.get(id) {
+                        Some(entry) => Ok(Ticket {
+                            is_stale: !is_fresh(entry),
+                            ..entry.ticket.clone()
+                        }),
+                        None => Err(GinspError::Cli(format!(
+                            "Ticket {} is not cached, run without --offline to fetch it",
+                            id
+                        ))),
+                    };
+                    (id.to_string(), ticket)
+                })
+                .collect();
+        }
+
+        let mut tickets = HashMap::new();
+        let mut missing = vec![];
+        for id in ids {
+            match entries.get(id) {
+                Some(entry) if self.mode == CacheMode::Default && is_fresh(entry) => {
+                    tickets.insert(id.to_string(), Ok(entry.ticket.clone()));
+                }
+                _ => missing.push(id.to_string()),
+            }
+        }
+
+        if missing.is_empty() {
+            return tickets;
+        }
+
+        let fetched = self.tracker.fetch_tickets(&missing);
+        let fetched_at: DateTime<Utc> = std::time::SystemTime::now().into();
+        for (id, result) in fetched {
+            if let Ok(ticket) = &result {
+                entries.insert(
+                    id.to_string(),
+                    CacheEntry {
+                        fetched_at,
+                        ticket: ticket.clone(),
+                    },
+                );
+            }
+            tickets.insert(id, result);
+        }
+        self.save(&entries);
+
+        tickets
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        self.tracker.ticket_url(id)
+    }
+
+    fn is_relative(&self, id: &str) -> bool {
+        self.tracker.is_relative(id)
+    }
+
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        self.tracker.validate_connection()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheMode, Cached};
+    use crate::config::ProjectManagement;
+    use crate::tracker::mock::{open_tracker, MockServer};
+    use crate::tracker::IssueTracker;
+    use std::path::{Path, PathBuf};
+
+    fn github_server() -> MockServer {
+        MockServer::start(|request| match request.path() {
+            "/repos/acme/web/issues/1" | "/repos/acme/web/issues/2" => (
+                200,
+                r#"{"state": "open", "labels": [], "milestone": null}"#.to_string(),
+            ),
+            _ => (404, "{}".to_string()),
+        })
+    }
+
+    fn cached(server: &MockServer, path: &Path, ttl: u64, mode: CacheMode) -> Cached {
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "GitHub"
+            url = "{}"
+            credential_key = "tok"
+            repository = "acme/web"
+            ticket_id_regex = '(#\d+)'
+            "#,
+            server.url
+        ));
+        Cached::new(tracker, Some(path.to_path_buf()), ttl, mode)
+    }
+
+    fn cache_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("ginsp-cache-{}", std::process::id()))
+            .join(format!("{}.json", name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn fresh_tickets_are_not_fetched_again() {
+        let server = github_server();
+        let path = cache_path("fresh");
+        let ids = ["#1", "#2"].map(str::to_string);
+
+        let tickets = cached(&server, &path, 3600, CacheMode::Default).fetch_tickets(&ids);
+        assert!(tickets.values().all(Result::is_ok));
+        assert_eq!(server.requests().len(), 2);
+
+        let tickets = cached(&server, &path, 3600, CacheMode::Default).fetch_tickets(&ids);
+        assert!(tickets
+            .values()
+            .all(|ticket| !ticket.as_ref().unwrap().is_stale));
+        assert_eq!(server.requests().len(), 2);
+
+        cached(&server, &path, 3600, CacheMode::Refresh).fetch_tickets(&ids);
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn offline_returns_stale_tickets_and_fails_uncached_ones() {
+        let server = github_server();
+        let path = cache_path("offline");
+
+        cached(&server, &path, 0, CacheMode::Default).fetch_tickets(&["#1".to_string()]);
+        assert_eq!(server.requests().len(), 1);
+
+        let ids = ["#1", "#2"].map(str::to_string);
+        let tickets = cached(&server, &path, 0, CacheMode::Offline).fetch_tickets(&ids);
+        assert!(tickets["#1"].as_ref().unwrap().is_stale);
+        assert!(tickets["#2"].is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn default_path_depends_on_the_repository() {
+        let path = |repository: &str| {
+            let project_management: ProjectManagement = toml::from_str(&format!(
+                r#"
+                provider = "GitHub"
+                url = "https://api.github.com"
+                credential_key = "tok"
+                repository = "{}"
+                ticket_id_regex = '(#\d+)'
+                "#,
+                repository
+            ))
+            .unwrap();
+            Cached::default_path(&project_management)
+        };
+
+        assert_ne!(path("acme/web"), path("acme/api"));
+    }
+}
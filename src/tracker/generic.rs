@@ -0,0 +1,179 @@
+use crate::config::{AuthType, GenericTracker, HttpMethod};
+use crate::error::{ConfigErrorKind, GinspError};
+use crate::tracker::{fetch_each, send_json, split_credential_key, Http, IssueTracker, Ticket};
+use std::collections::HashMap;
+
+/// Any REST tracker configured in `[project_management.generic]`,
+/// `url` is the ticket endpoint with a `:ticket_id` placeholder.
+pub struct Generic {
+    url: String,
+    credential_key: String,
+    config: GenericTracker,
+    http: Http,
+}
+
+impl Generic {
+    pub fn new(
+        url: String,
+        credential_key: String,
+        config: GenericTracker,
+        http: Http,
+    ) -> Result<Self, GinspError> {
+        let pointers = [
+            Some(&config.status),
+            config.title.as_ref(),
+            config.assignee.as_ref(),
+            config.kind.as_ref(),
+        ];
+        for pointer in pointers.into_iter().flatten() {
+            // the same rule as `serde_json::Value::pointer`
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                return Err(GinspError::Config(ConfigErrorKind::InvalidJsonPointer(
+                    pointer.to_string(),
+                )));
+            }
+        }
+
+        if let AuthType::Basic = config.auth_type {
+            split_credential_key(&credential_key)?;
+        }
+
+        Ok(Self {
+            url,
+            credential_key,
+            config,
+            http,
+        })
+    }
+
+    fn request(&self, id: &str) -> reqwest::blocking::RequestBuilder {
+        let url = self.url.replace(":ticket_id", id);
+        let mut request = match self.config.method {
+            HttpMethod::Get => self.http.client().get(url),
+            HttpMethod::Post => self.http.client().post(url),
+        };
+
+        request = match self.config.auth_type {
+            AuthType::Basic => match split_credential_key(&self.credential_key) {
+                Ok((username, password)) => request.basic_auth(username, Some(password)),
+                Err(_) => request,
+            },
+            AuthType::Bearer => request.bearer_auth(&self.credential_key),
+            AuthType::None => request,
+        };
+
+        request = request.header("Accept", "application/json");
+        for (name, value) in self.config.headers.iter() {
+            request = request.header(name, value.replace(":credential_key", &self.credential_key));
+        }
+
+        if let Some(body) = &self.config.body {
+            request = request.body(body.replace(":ticket_id", id));
+        }
+
+        request
+    }
+}
+
+/// Value at the JSON pointer, numbers and other values as their JSON text.
+fn read_pointer(json: &serde_json::Value, pointer: &str) -> Option<String> {
+    match json.pointer(pointer)? {
+        serde_json::Value::Null => None,
+        value => Some(
+            value
+                .as_str()
+                .map_or_else(|| value.to_string(), str::to_string),
+        ),
+    }
+}
+
+impl IssueTracker for Generic {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        let json = send_json(self.request(id))?;
+
+        let status = read_pointer(&json, &self.config.status).ok_or(GinspError::Http(format!(
+            "Error: status not found at {}",
+            self.config.status
+        )))?;
+        let read_optional = |pointer: &Option<String>| {
+            pointer
+                .as_ref()
+                .and_then(|pointer| read_pointer(&json, pointer))
+        };
+
+        Ok(Ticket {
+            title: read_optional(&self.config.title),
+            assignee: read_optional(&self.config.assignee),
+            kind: read_optional(&self.config.kind),
+            ..Ticket::with_status(status)
+        })
+    }
+
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        fetch_each(&self.http, ids, |id| self.fetch_ticket(id))
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        self.url.replace(":ticket_id", id)
+    }
+
+    /// Only the config is checked, there is no endpoint to call without a ticket id.
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        if !self.url.contains(":ticket_id") {
+            return Err(GinspError::Cli(
+                "The url of the Generic provider has no :ticket_id placeholder".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracker::mock::{open_tracker, MockServer};
+
+    #[test]
+    fn fetch_ticket_reads_the_json_pointers() {
+        let server = MockServer::start(|request| match request.path() {
+            "/issues/42" => (
+                200,
+                r#"{"issue": {"status": {"name": "Resolved"}, "subject": "Fix login",
+                    "assigned_to": null, "tracker": {"id": 1}}}"#
+                    .to_string(),
+            ),
+            _ => (404, "{}".to_string()),
+        });
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "Generic"
+            url = "{}/issues/:ticket_id"
+            credential_key = "key"
+            ticket_id_regex = '#(\d+)'
+
+            [generic]
+            method = "Post"
+            auth_type = "None"
+            headers = {{ "X-Api-Key" = "Token :credential_key" }}
+            body = '{{"id": ":ticket_id"}}'
+            status = "/issue/status/name"
+            title = "/issue/subject"
+            assignee = "/issue/assigned_to/name"
+            type = "/issue/tracker/id"
+            "#,
+            server.url
+        ));
+
+        let ticket = tracker.fetch_ticket("42").unwrap();
+        assert_eq!(ticket.status, "Resolved");
+        assert_eq!(ticket.title.as_deref(), Some("Fix login"));
+        assert_eq!(ticket.assignee, None);
+        assert_eq!(ticket.kind.as_deref(), Some("1"));
+        assert!(tracker.fetch_ticket("43").is_err());
+
+        let request = &server.requests()[0];
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.header("x-api-key"), Some("Token key"));
+        assert_eq!(request.header("authorization"), None);
+        assert_eq!(request.body, r#"{"id": "42"}"#);
+    }
+}
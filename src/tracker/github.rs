@@ -0,0 +1,138 @@
+use crate::error::GinspError;
+use crate::tracker::{
+    fetch_each, issue_status, parse_issue_ref, send_json, Http, IssueTracker, Ticket,
+};
+use std::collections::HashMap;
+
+/// GitHub Issues REST API, `url` is the API root, e.g. `https://api.github.com`
+/// or `https://github.example.com/api/v3` for GitHub Enterprise Server.
+/// Tickets are `owner/repo#123`, or `#123` in the configured repository.
+pub struct GitHub {
+    url: String,
+    token: String,
+    repository: Option<String>,
+    http: Http,
+}
+
+impl GitHub {
+    pub fn new(url: String, token: String, repository: Option<String>, http: Http) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            repository,
+            http,
+        }
+    }
+
+    /// `https://github.com` for `https://api.github.com`, the host without `/api/v3` otherwise.
+    fn web_url(&self) -> String {
+        self.url
+            .trim_end_matches("/api/v3")
+            .replace("://api.github.com", "://github.com")
+    }
+
+    fn get_json(&self, url: &str) -> Result<serde_json::Value, GinspError> {
+        let mut request = self
+            .http
+            .client()
+            .get(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "ginsp");
+        if !self.token.is_empty() {
+            request = request.bearer_auth(&self.token);
+        }
+        send_json(request)
+    }
+}
+
+impl IssueTracker for GitHub {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        let (repository, number) = parse_issue_ref(id, self.repository.as_deref())?;
+        let json = self.get_json(&format!(
+            "{}/repos/{}/issues/{}",
+            self.url, repository, number
+        ))?;
+
+        let state = json["state"]
+            .as_str()
+            .ok_or(GinspError::Http("Error: state not found".to_string()))?;
+        let labels = json["labels"]
+            .as_array()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label["name"].as_str())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let milestone = json["milestone"]["title"].as_str();
+
+        Ok(Ticket::with_status(issue_status(state, &labels, milestone)))
+    }
+
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        fetch_each(&self.http, ids, |id| self.fetch_ticket(id))
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        match parse_issue_ref(id, self.repository.as_deref()) {
+            Ok((repository, number)) => {
+                format!("{}/{}/issues/{}", self.web_url(), repository, number)
+            }
+            Err(_) => self.web_url(),
+        }
+    }
+
+    fn is_relative(&self, id: &str) -> bool {
+        id.starts_with('#')
+    }
+
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        self.get_json(&format!("{}/rate_limit", self.url))
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracker::mock::{open_tracker, MockServer};
+
+    #[test]
+    fn fetch_ticket_formats_state_labels_and_milestone() {
+        let server = MockServer::start(|request| {
+            match request.path() {
+            "/repos/acme/web/issues/12" => (
+                200,
+                r#"{"state": "closed", "labels": [{"name": "bug"}, {"name": "ui"}], "milestone": {"title": "v1.23"}}"#
+                    .to_string(),
+            ),
+            "/repos/acme/other/issues/3" => (200, r#"{"state": "open", "labels": [], "milestone": null}"#.to_string()),
+            _ => (404, "{}".to_string()),
+        }
+        });
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "GitHub"
+            url = "{}"
+            credential_key = "tok"
+            repository = "acme/web"
+            ticket_id_regex = '(#\d+)'
+            "#,
+            server.url
+        ));
+
+        let ticket = tracker.fetch_ticket("#12").unwrap();
+        assert_eq!(ticket.status, "closed [bug, ui] (v1.23)");
+        let ticket = tracker.fetch_ticket("acme/other#3").unwrap();
+        assert_eq!(ticket.status, "open");
+        assert!(tracker.fetch_ticket("#404").is_err());
+
+        let request = &server.requests()[0];
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.header("authorization"), Some("Bearer tok"));
+        assert_eq!(
+            request.header("accept"),
+            Some("application/vnd.github+json")
+        );
+    }
+}
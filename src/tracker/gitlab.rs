@@ -0,0 +1,127 @@
+use crate::error::GinspError;
+use crate::tracker::{
+    fetch_each, issue_status, parse_issue_ref, send_json, Http, IssueTracker, Ticket,
+};
+use std::collections::HashMap;
+
+/// GitLab v4 issues API, `url` is the API root, e.g. `https://gitlab.com/api/v4`
+/// or `https://gitlab.example.com/api/v4` for self-hosted instances.
+/// Tickets are `group/project#123`, or `#123` in the configured project.
+pub struct GitLab {
+    url: String,
+    token: String,
+    repository: Option<String>,
+    http: Http,
+}
+
+impl GitLab {
+    pub fn new(url: String, token: String, repository: Option<String>, http: Http) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            repository,
+            http,
+        }
+    }
+
+    /// The instance URL, without `/api/v4`.
+    fn web_url(&self) -> &str {
+        self.url.trim_end_matches("/api/v4")
+    }
+
+    fn get_json(&self, url: &str) -> Result<serde_json::Value, GinspError> {
+        send_json(
+            self.http
+                .client()
+                .get(url)
+                .header("Accept", "application/json")
+                .header("PRIVATE-TOKEN", &self.token),
+        )
+    }
+}
+
+impl IssueTracker for GitLab {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        let (project, iid) = parse_issue_ref(id, self.repository.as_deref())?;
+        // the project path is a single URL-encoded segment
+        let json = self.get_json(&format!(
+            "{}/projects/{}/issues/{}",
+            self.url,
+            project.replace('/', "%2F"),
+            iid
+        ))?;
+
+        let state = json["state"]
+            .as_str()
+            .ok_or(GinspError::Http("Error: state not found".to_string()))?;
+        let labels = json["labels"]
+            .as_array()
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label.as_str())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let milestone = json["milestone"]["title"].as_str();
+
+        Ok(Ticket::with_status(issue_status(state, &labels, milestone)))
+    }
+
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        fetch_each(&self.http, ids, |id| self.fetch_ticket(id))
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        match parse_issue_ref(id, self.repository.as_deref()) {
+            Ok((project, iid)) => format!("{}/{}/-/issues/{}", self.web_url(), project, iid),
+            Err(_) => self.web_url().to_string(),
+        }
+    }
+
+    fn is_relative(&self, id: &str) -> bool {
+        id.starts_with('#')
+    }
+
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        self.get_json(&format!("{}/user", self.url)).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracker::mock::{open_tracker, MockServer};
+
+    #[test]
+    fn fetch_tickets_encodes_the_project_path() {
+        let server = MockServer::start(|request| match request.path() {
+            "/api/v4/projects/group%2Fsub%2Fapp/issues/7" => (
+                200,
+                r#"{"state": "opened", "labels": ["backend"], "milestone": {"title": "2026.10"}}"#
+                    .to_string(),
+            ),
+            _ => (404, "{}".to_string()),
+        });
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "GitLab"
+            url = "{}/api/v4"
+            credential_key = "gl"
+            repository = "group/sub/app"
+            ticket_id_regex = '(#\d+)'
+            "#,
+            server.url
+        ));
+
+        let tickets = tracker.fetch_tickets(&["#7".to_string(), "#8".to_string()]);
+        assert_eq!(
+            tickets["#7"].as_ref().unwrap().status,
+            "opened [backend] (2026.10)"
+        );
+        assert!(tickets["#8"].is_err());
+        assert!(server
+            .requests()
+            .iter()
+            .all(|request| request.header("private-token") == Some("gl")));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::config::ProjectManagement;
+use crate::error::GinspError;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::StatusCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Requests sent at once when `concurrency` is not set.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Seconds a request may take when `timeout` is not set.
+const DEFAULT_TIMEOUT: u64 = 30;
+
+/// Retries of a throttled request before giving up.
+const MAX_RETRIES: u32 = 3;
+
+/// Longest wait before retrying a throttled request, whatever `Retry-After` asks for.
+/// Tests wait at most 100ms so that retries do not slow the suite down.
+const MAX_RETRY_DELAY: Duration = if cfg!(test) {
+    Duration::from_millis(100)
+} else {
+    Duration::from_secs(60)
+};
+
+/// HTTP client of the trackers, with the timeout and concurrency of the config file.
+pub struct Http {
+    client: Client,
+    concurrency: usize,
+}
+
+impl Http {
+    pub fn new(project_management: &ProjectManagement) -> Result<Self, GinspError> {
+        let timeout = project_management.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        let client = Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()
+            .map_err(|err| GinspError::Http(err.to_string()))?;
+
+        Ok(Self {
+            client,
+            concurrency: project_management
+                .concurrency
+                .unwrap_or(DEFAULT_CONCURRENCY)
+                .max(1),
+        })
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Run `fetch` on every item, with at most `concurrency` items in flight.
+    /// The results are in the order of the items.
+    pub fn for_each<T: Sync, R: Send>(
+        &self,
+        items: &[T],
+        fetch: impl Fn(&T) -> R + Sync,
+    ) -> Vec<R> {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(items.len()));
+
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(items.len()) {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result = fetch(item);
+                    results
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push((index, result));
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap_or_else(PoisonError::into_inner);
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+/// Send the request and parse the JSON body of a successful response.
+/// Throttled requests (429 or 503) are retried after `Retry-After`, or with a backoff.
+pub fn send_json(request: RequestBuilder) -> Result<serde_json::Value, GinspError> {
+    let mut attempt = 0;
+    let res = loop {
+        let res = request
+            .try_clone()
+            .ok_or(GinspError::System("Request cannot be sent".to_string()))?
+            .send()
+            .map_err(|err| GinspError::Http(err.to_string()))?;
+
+        let is_throttled = matches!(
+            res.status(),
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        );
+        if !is_throttled || attempt >= MAX_RETRIES {
+            break res;
+        }
+
+        let delay = retry_after(&res)
+            .unwrap_or(Duration::from_secs(1 << attempt))
+            .min(MAX_RETRY_DELAY);
+        thread::sleep(delay);
+        attempt += 1;
+    };
+
+    let status = res.status();
+
+    if status.is_success() {
+        let body = res
+            .text()
+            .map_err(|err| GinspError::System(err.to_string()))?;
+        serde_json::from_str(&body).map_err(|err| GinspError::System(err.to_string()))
+    } else {
+        Err(GinspError::Http(format!("Error: {}", status)))
+    }
+}
+
+/// Delay of a `Retry-After: <seconds>` header.
+fn retry_after(res: &Response) -> Option<Duration> {
+    let seconds = res
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Copy of the error of a batch request, for each ticket of the batch.
+pub fn copy_error(err: &GinspError) -> GinspError {
+    match err {
+        GinspError::Http(message) => GinspError::Http(message.to_string()),
+        err => GinspError::System(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{send_json, Http, MAX_RETRIES, MAX_RETRY_DELAY};
+    use crate::config::ProjectManagement;
+    use crate::tracker::mock::{MockServer, Response};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    fn http(concurrency: usize) -> Http {
+        let project_management: ProjectManagement = toml::from_str(&format!(
+            r#"
+            provider = "Generic"
+            url = "http://localhost"
+            credential_key = ""
+            ticket_id_regex = '(\d+)'
+            concurrency = {}
+            "#,
+            concurrency
+        ))
+        .unwrap();
+        Http::new(&project_management).unwrap()
+    }
+
+    fn throttled(status: u16, retry_after: Option<&str>) -> Response {
+        Response {
+            status,
+            headers: retry_after
+                .map(|seconds| ("Retry-After".to_string(), seconds.to_string()))
+                .into_iter()
+                .collect(),
+            body: "{}".to_string(),
+        }
+    }
+
+    #[test]
+    fn send_json_retries_throttled_requests_after_retry_after() {
+        let count = AtomicUsize::new(0);
+        let server = MockServer::start(move |_| match count.fetch_add(1, Ordering::Relaxed) {
+            0 => throttled(429, Some("0")),
+            1 => throttled(503, Some("0")),
+            _ => (200, r#"{"ok": true}"#.to_string()).into(),
+        });
+
+        let started = Instant::now();
+        let json = send_json(http(1).client().get(&server.url)).unwrap();
+
+        assert_eq!(json["ok"], true);
+        assert_eq!(server.requests().len(), 3);
+        // without `Retry-After` the backoff would wait at least MAX_RETRY_DELAY
+        assert!(started.elapsed() < MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn send_json_stops_retrying_after_max_retries() {
+        let server = MockServer::start(|_| throttled(429, None));
+
+        let started = Instant::now();
+        let result = send_json(http(1).client().get(&server.url));
+
+        assert!(result.is_err());
+        assert_eq!(server.requests().len(), MAX_RETRIES as usize + 1);
+        assert!(started.elapsed() >= MAX_RETRY_DELAY * MAX_RETRIES);
+    }
+
+    #[test]
+    fn for_each_keeps_the_order_of_the_items() {
+        let server = MockServer::start(|request| {
+            let index = request.path().trim_start_matches('/');
+            (200, format!(r#"{{"index": {}}}"#, index))
+        });
+        let http = http(2);
+        let items = (0..10).collect::<Vec<u64>>();
+
+        let results = http.for_each(&items, |item| {
+            let json = send_json(http.client().get(format!("{}/{}", server.url, item))).unwrap();
+            json["index"].as_u64().unwrap()
+        });
+
+        assert_eq!(results, items);
+        assert_eq!(server.requests().len(), items.len());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::error::GinspError;
+use crate::tracker::{fetch_each, send_json, Http, IssueTracker, Ticket};
+use std::collections::HashMap;
+
+/// Issues looked up in one `/search` request.
+const BATCH_SIZE: usize = 50;
+
+/// Issue fields read into a `Ticket`.
+const FIELDS: &str = "status,summary,issuetype,priority,assignee,fixVersions,labels,resolution";
+
+/// Jira REST API, `url` is the issue endpoint with a `:ticket_id` placeholder,
+/// e.g. `https://my-org.atlassian.net/rest/api/3/issue/:ticket_id`.
+pub struct Jira {
+    url: String,
+    username: String,
+    password: String,
+    http: Http,
+    /// Print the errors of failed searches, which are otherwise replaced by issue lookups.
+    is_verbose: bool,
+}
+
+impl Jira {
+    pub fn new(
+        url: String,
+        username: String,
+        password: String,
+        http: Http,
+        is_verbose: bool,
+    ) -> Self {
+        Self {
+            url,
+            username,
+            password,
+            http,
+            is_verbose,
+        }
+    }
+
+    /// `https://my-org.atlassian.net/rest/api/3`, the part of the issue URL before `/issue/`.
+    fn api_url(&self) -> &str {
+        self.url
+            .split_once("/issue/")
+            .map_or(self.url.as_str(), |(api_url, _)| api_url)
+    }
+
+    /// `https://my-org.atlassian.net`, the part of the issue URL before `/rest/`.
+    fn site_url(&self) -> &str {
+        self.url
+            .split_once("/rest/")
+            .map_or(self.url.as_str(), |(site_url, _)| site_url)
+    }
+
+    /// Whether the issue URL is on the v3 API of Jira Cloud, where `/search` is replaced
+    /// by `/search/jql`, rather than the v2 API of Jira Server and Data Center.
+    fn is_cloud_api(&self) -> bool {
+        self.api_url().ends_with("/api/3")
+    }
+
+    fn get_json(&self, url: &str, query: &[(&str, &str)]) -> Result<serde_json::Value, GinspError> {
+        send_json(
+            self.http
+                .client()
+                .get(url)
+                .query(query)
+                .header("Accept", "application/json")
+                .basic_auth(&self.username, Some(&self.password)),
+        )
+    }
+
+    /// Look up the issues in one JQL search, by key.
+    /// Unknown keys are left out of the result instead of failing the search.
+    fn search(&self, keys: &[String]) -> Result<HashMap<String, Ticket>, GinspError> {
+        let jql = format!(
+            "key in ({})",
+            keys.iter()
+                .map(|key| format!("\"{}\"", key.replace('"', "")))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let max_results = keys.len().to_string();
+        let json = if self.is_cloud_api() {
+            self.get_json(
+                &format!("{}/search/jql", self.api_url()),
+                &[
+                    ("jql", jql.as_str()),
+                    ("fields", FIELDS),
+                    ("maxResults", max_results.as_str()),
+                ],
+            )?
+        } else {
+            self.get_json(
+                &format!("{}/search", self.api_url()),
+                &[
+                    ("jql", jql.as_str()),
+                    ("fields", FIELDS),
+                    ("maxResults", max_results.as_str()),
+                    ("validateQuery", "warn"),
+                ],
+            )?
+        };
+
+        Ok(json["issues"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|issue| {
+                let key = issue["key"].as_str()?;
+                let ticket = to_ticket(&issue["fields"]).ok()?;
+                Some((key.to_string(), ticket))
+            })
+            .collect())
+    }
+}
+
+fn to_ticket(fields: &serde_json::Value) -> Result<Ticket, GinspError> {
+    let fields = fields
+        .as_object()
+        .ok_or(GinspError::Http("Error: fields not found".to_string()))?;
+    let status = fields["status"]["name"]
+        .as_str()
+        .ok_or(GinspError::Http("Error: status not found".to_string()))?;
+    let text = |value: &serde_json::Value| value.as_str().map(str::to_string);
+    let names = |value: &serde_json::Value| {
+        value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_str().or(item["name"].as_str()))
+            .map(str::to_string)
+            .collect()
+    };
+
+    Ok(Ticket {
+        title: fields.get("summary").and_then(text),
+        assignee: fields
+            .get("assignee")
+            .and_then(|user| text(&user["displayName"])),
+        kind: fields.get("issuetype").and_then(|kind| text(&kind["name"])),
+        priority: fields
+            .get("priority")
+            .and_then(|priority| text(&priority["name"])),
+        fix_versions: fields.get("fixVersions").map(names).unwrap_or_default(),
+        labels: fields.get("labels").map(names).unwrap_or_default(),
+        resolution: fields
+            .get("resolution")
+            .and_then(|resolution| text(&resolution["name"])),
+        ..Ticket::with_status(status.to_string())
+    })
+}
+
+impl IssueTracker for Jira {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        let json = self.get_json(&self.url.replace(":ticket_id", id), &[("fields", FIELDS)])?;
+        to_ticket(&json["fields"])
+    }
+
+    /// Search the tickets in concurrent batches, then fetch the ones the search did not
+    /// return (e.g. moved issues with a new key, or a failed search) one by one.
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        let chunks = ids.chunks(BATCH_SIZE).collect::<Vec<_>>();
+        let mut found = self
+            .http
+            .for_each(&chunks, |chunk| {
+                self.search(chunk).unwrap_or_else(|err| {
+                    if self.is_verbose {
+                        eprintln!(
+                            "Fail to search tickets {}..{}, fetching them one by one. Error: {}",
+                            chunk[0],
+                            chunk[chunk.len() - 1],
+                            err
+                        );
+                    }
+                    HashMap::new()
+                })
+            })
+            .into_iter()
+            .flatten()
+            .collect::<HashMap<_, _>>();
+
+        let missing = ids
+            .iter()
+            .filter(|id| !found.contains_key(*id))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut tickets = fetch_each(&self.http, &missing, |id| self.fetch_ticket(id));
+        for id in ids {
+            if let Some(ticket) = found.remove(id) {
+                tickets.insert(id.to_string(), Ok(ticket));
+            }
+        }
+        tickets
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        format!("{}/browse/{}", self.site_url(), id)
+    }
+
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        self.get_json(&format!("{}/myself", self.api_url()), &[])
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracker::mock::{open_tracker, MockServer};
+
+    const ISSUE_FIELDS: &str = r#"{"status": {"name": "Done"}, "summary": "Fix login",
+        "issuetype": {"name": "Bug"}, "priority": {"name": "High"},
+        "assignee": {"displayName": "Jane Doe"}, "fixVersions": [{"name": "v1.2"}],
+        "labels": ["backend"], "resolution": {"name": "Fixed"}}"#;
+
+    #[test]
+    fn fetch_tickets_searches_then_fetches_the_missing_ones() {
+        let server = MockServer::start(|request| match request.path() {
+            "/rest/api/3/search/jql" => (
+                200,
+                format!(
+                    r#"{{"issues": [{{"key": "ABC-1", "fields": {}}}]}}"#,
+                    ISSUE_FIELDS
+                ),
+            ),
+            "/rest/api/3/issue/OLD-7" => (
+                200,
+                r#"{"key": "NEW-7", "fields": {"status": {"name": "In Progress"}}}"#.to_string(),
+            ),
+            _ => (404, "{}".to_string()),
+        });
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "Jira"
+            url = "{}/rest/api/3/issue/:ticket_id"
+            credential_key = "me@x:secret"
+            ticket_id_regex = '(\w+-\d+)'
+            "#,
+            server.url
+        ));
+
+        let ids = ["ABC-1", "OLD-7", "ABC-404"].map(str::to_string);
+        let tickets = tracker.fetch_tickets(&ids);
+
+        let ticket = tickets["ABC-1"].as_ref().unwrap();
+        assert_eq!(ticket.status, "Done");
+        assert_eq!(ticket.title.as_deref(), Some("Fix login"));
+        assert_eq!(ticket.kind.as_deref(), Some("Bug"));
+        assert_eq!(ticket.priority.as_deref(), Some("High"));
+        assert_eq!(ticket.assignee.as_deref(), Some("Jane Doe"));
+        assert_eq!(ticket.fix_versions, ["v1.2"]);
+        assert_eq!(ticket.labels, ["backend"]);
+        assert_eq!(ticket.resolution.as_deref(), Some("Fixed"));
+        assert_eq!(tickets["OLD-7"].as_ref().unwrap().status, "In Progress");
+        assert!(tickets["ABC-404"].is_err());
+
+        let requests = server.requests();
+        let searches = requests
+            .iter()
+            .filter(|request| request.path().ends_with("/search/jql"))
+            .collect::<Vec<_>>();
+        assert_eq!(searches.len(), 1);
+        // `me@x:secret` in base64
+        assert_eq!(
+            searches[0].header("authorization"),
+            Some("Basic bWVAeDpzZWNyZXQ=")
+        );
+        assert_eq!(requests.len(), 3);
+    }
+}
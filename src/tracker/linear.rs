@@ -0,0 +1,202 @@
+use crate::error::GinspError;
+use crate::tracker::{fetch_each, send_json, Http, IssueTracker, Ticket};
+use std::collections::HashMap;
+
+/// Issues looked up in one GraphQL request.
+const BATCH_SIZE: usize = 50;
+
+const ISSUE_FIELDS: &str = "identifier state { name } assignee { name } cycle { name number }";
+
+/// Linear GraphQL API, `url` is the endpoint, e.g. `https://api.linear.app/graphql`.
+/// Tickets are issue identifiers like `ENG-123`.
+pub struct Linear {
+    url: String,
+    api_key: String,
+    /// URL key of the Linear organization, for ticket links.
+    organization: Option<String>,
+    http: Http,
+}
+
+impl Linear {
+    pub fn new(url: String, api_key: String, organization: Option<String>, http: Http) -> Self {
+        Self {
+            url,
+            api_key,
+            organization,
+            http,
+        }
+    }
+
+    fn query(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value, GinspError> {
+        let json = send_json(
+            self.http
+                .client()
+                .post(&self.url)
+                .header("Content-Type", "application/json")
+                .header("Authorization", &self.api_key)
+                .body(serde_json::json!({ "query": query, "variables": variables }).to_string()),
+        )?;
+
+        // `issue` is non-null in the schema, so a single unknown issue
+        // nulls the whole `data` of the request
+        if json["data"].is_null() {
+            let message = json["errors"][0]["message"]
+                .as_str()
+                .unwrap_or("data not found");
+            return Err(GinspError::Http(format!("Error: {}", message)));
+        }
+        Ok(json)
+    }
+
+    /// Look up the issues in one request, with one aliased `issue` field per id.
+    fn fetch_batch(&self, ids: &[String]) -> Result<serde_json::Value, GinspError> {
+        let params = (0..ids.len())
+            .map(|index| format!("$i{}: String!", index))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let fields = (0..ids.len())
+            .map(|index| format!("i{0}: issue(id: $i{0}) {{ {1} }}", index, ISSUE_FIELDS))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let variables = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (format!("i{}", index), serde_json::json!(id)))
+            .collect::<serde_json::Map<_, _>>();
+
+        self.query(
+            &format!("query({}) {{ {} }}", params, fields),
+            serde_json::Value::Object(variables),
+        )
+    }
+}
+
+/// Workflow state, assignee, and cycle of an issue, e.g. `In Review @Jane Doe (Cycle 12)`.
+fn to_ticket(issue: &serde_json::Value) -> Result<Ticket, GinspError> {
+    let state = issue["state"]["name"]
+        .as_str()
+        .ok_or(GinspError::Http("Error: issue not found".to_string()))?;
+
+    let assignee = issue["assignee"]["name"].as_str();
+
+    let mut status = state.to_string();
+    if let Some(assignee) = assignee {
+        status.push_str(&format!(" @{}", assignee));
+    }
+    let cycle = &issue["cycle"];
+    match (cycle["name"].as_str(), cycle["number"].as_u64()) {
+        (Some(name), _) => status.push_str(&format!(" ({})", name)),
+        (None, Some(number)) => status.push_str(&format!(" (Cycle {})", number)),
+        (None, None) => {}
+    }
+
+    Ok(Ticket {
+        assignee: assignee.map(str::to_string),
+        ..Ticket::with_status(status)
+    })
+}
+
+impl IssueTracker for Linear {
+    fn fetch_ticket(&self, id: &str) -> Result<Ticket, GinspError> {
+        let json = self.fetch_batch(&[id.to_string()])?;
+        to_ticket(&json["data"]["i0"])
+    }
+
+    /// Look up the tickets in concurrent batches, then the tickets of failed batches
+    /// (e.g. with an id that is not a Linear issue) one by one.
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>> {
+        let chunks = ids.chunks(BATCH_SIZE).collect::<Vec<_>>();
+        let batches = self.http.for_each(&chunks, |chunk| self.fetch_batch(chunk));
+
+        let mut tickets = HashMap::new();
+        let mut missing = vec![];
+        for (chunk, batch) in chunks.into_iter().zip(batches) {
+            match batch {
+                Ok(json) => {
+                    for (index, id) in chunk.iter().enumerate() {
+                        let issue = &json["data"][format!("i{}", index)];
+                        tickets.insert(id.to_string(), to_ticket(issue));
+                    }
+                }
+                Err(_) => missing.extend(chunk.iter().cloned()),
+            }
+        }
+
+        tickets.extend(fetch_each(&self.http, &missing, |id| self.fetch_ticket(id)));
+        tickets
+    }
+
+    fn ticket_url(&self, id: &str) -> String {
+        match &self.organization {
+            Some(organization) => format!("https://linear.app/{}/issue/{}", organization, id),
+            None => format!("https://linear.app/issue/{}", id),
+        }
+    }
+
+    fn validate_connection(&self) -> Result<(), GinspError> {
+        self.query("query { viewer { id } }", serde_json::json!({}))
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracker::mock::{open_tracker, MockServer};
+
+    /// Like Linear, an unknown issue nulls the `data` of the whole request.
+    fn graphql(body: &str) -> (u16, String) {
+        let request: serde_json::Value = serde_json::from_str(body).unwrap();
+        let variables = request["variables"].as_object().unwrap();
+        if variables.values().any(|id| id == "UTF-8") {
+            return (
+                200,
+                r#"{"data": null, "errors": [{"message": "Entity not found"}]}"#.to_string(),
+            );
+        }
+        let data = variables
+            .iter()
+            .map(|(alias, _)| {
+                let issue = serde_json::json!({
+                    "state": {"name": "In Review"},
+                    "assignee": {"name": "Jane Doe"},
+                    "cycle": {"name": null, "number": 12}
+                });
+                (alias.to_string(), issue)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        (200, serde_json::json!({ "data": data }).to_string())
+    }
+
+    #[test]
+    fn fetch_tickets_falls_back_to_single_lookups() {
+        let server = MockServer::start(|request| graphql(&request.body));
+        let tracker = open_tracker(&format!(
+            r#"
+            provider = "Linear"
+            url = "{}/graphql"
+            credential_key = "lin_key"
+            ticket_id_regex = '([A-Z]+-\d+)'
+            "#,
+            server.url
+        ));
+
+        let ids = ["ENG-1", "ENG-2", "UTF-8"].map(str::to_string);
+        let tickets = tracker.fetch_tickets(&ids);
+
+        let ticket = tickets["ENG-1"].as_ref().unwrap();
+        assert_eq!(ticket.status, "In Review @Jane Doe (Cycle 12)");
+        assert_eq!(ticket.assignee.as_deref(), Some("Jane Doe"));
+        assert!(tickets["ENG-2"].is_ok());
+        assert!(tickets["UTF-8"].is_err());
+
+        let requests = server.requests();
+        // the failed batch, then one lookup per id
+        assert_eq!(requests.len(), 4);
+        assert!(requests.iter().all(|request| request.method == "POST"
+            && request.header("authorization") == Some("lin_key")));
+    }
+}
@@ -0,0 +1,227 @@
+mod azure_devops;
+mod cache;
+mod generic;
+mod github;
+mod gitlab;
+mod http;
+mod jira;
+mod linear;
+#[cfg(test)]
+mod mock;
+
+pub use azure_devops::AzureDevOps;
+pub use cache::{CacheMode, Cached};
+pub use generic::Generic;
+pub use github::GitHub;
+pub use gitlab::GitLab;
+pub use http::Http;
+pub use jira::Jira;
+pub use linear::Linear;
+
+use crate::config::{ProjectManagement, ProjectManagementProvider};
+use crate::error::{ConfigErrorKind, GinspError};
+use http::{copy_error, send_json};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A ticket as fetched from the issue tracker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ticket {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fix_versions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    /// Read from the cache with `--offline`, cached longer than the TTL.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_stale: bool,
+}
+
+impl Ticket {
+    /// A ticket with only a status, as shown in the ticket status column.
+    pub fn with_status(status: String) -> Self {
+        Self {
+            status,
+            title: None,
+            assignee: None,
+            kind: None,
+            priority: None,
+            fix_versions: vec![],
+            labels: vec![],
+            resolution: None,
+            is_stale: false,
+        }
+    }
+
+    /// Status shown in the ticket status column, marked when it may be out of date.
+    pub fn status_label(&self) -> String {
+        if self.is_stale {
+            format!("{} (stale)", self.status)
+        } else {
+            self.status.to_string()
+        }
+    }
+}
+
+/// Seconds a cached ticket is used for when `cache_ttl` is not set.
+const DEFAULT_CACHE_TTL: u64 = 3600;
+
+/// An issue tracker that tickets referenced in commit messages are fetched from.
+pub trait IssueTracker {
+    fn fetch_ticket(&self, id: &str) -> anyhow::Result<Ticket, GinspError>;
+
+    /// Fetch several tickets at once, concurrently or in batches.
+    fn fetch_tickets(&self, ids: &[String]) -> HashMap<String, Result<Ticket, GinspError>>;
+
+    /// Link to the ticket in the tracker's web UI.
+    fn ticket_url(&self, id: &str) -> String;
+
+    /// Whether the ticket id is relative to the configured repository, e.g. `#123`.
+    fn is_relative(&self, _id: &str) -> bool {
+        false
+    }
+
+    /// Check that the tracker can be reached with the configured credentials.
+    fn validate_connection(&self) -> anyhow::Result<(), GinspError>;
+}
+
+/// Open the issue tracker selected by `provider` in the config file.
+/// With `is_verbose`, requests that fail without failing a ticket print their errors.
+pub fn open(
+    project_management: &ProjectManagement,
+    is_verbose: bool,
+) -> anyhow::Result<Box<dyn IssueTracker>, GinspError> {
+    let http = Http::new(project_management)?;
+    match project_management.provider {
+        ProjectManagementProvider::Jira => {
+            let (username, password) = split_credential_key(&project_management.credential_key)?;
+            Ok(Box::new(Jira::new(
+                project_management.url.to_string(),
+                username.to_string(),
+                password.to_string(),
+                http,
+                is_verbose,
+            )))
+        }
+        ProjectManagementProvider::GitHub => Ok(Box::new(GitHub::new(
+            project_management.url.to_string(),
+            project_management.credential_key.to_string(),
+            project_management.repository.clone(),
+            http,
+        ))),
+        ProjectManagementProvider::GitLab => Ok(Box::new(GitLab::new(
+            project_management.url.to_string(),
+            project_management.credential_key.to_string(),
+            project_management.repository.clone(),
+            http,
+        ))),
+        ProjectManagementProvider::Linear => Ok(Box::new(Linear::new(
+            project_management.url.to_string(),
+            project_management.credential_key.to_string(),
+            project_management.organization.clone(),
+            http,
+        ))),
+        ProjectManagementProvider::Generic => {
+            let generic = project_management.generic.as_ref().ok_or(GinspError::Cli(
+                "Missing [project_management.generic] in config file".to_string(),
+            ))?;
+            Ok(Box::new(Generic::new(
+                project_management.url.to_string(),
+                project_management.credential_key.to_string(),
+                generic.clone(),
+                http,
+            )?))
+        }
+        ProjectManagementProvider::AzureDevOps => {
+            let (Some(organization), Some(project)) = (
+                &project_management.organization,
+                &project_management.project,
+            ) else {
+                return Err(GinspError::Cli(
+                    "Set `organization` and `project` in [project_management] for Azure DevOps"
+                        .to_string(),
+                ));
+            };
+            Ok(Box::new(AzureDevOps::new(
+                project_management.url.to_string(),
+                project_management.credential_key.to_string(),
+                organization.to_string(),
+                project.to_string(),
+                http,
+            )))
+        }
+    }
+}
+
+/// Open the issue tracker with its tickets cached for `cache_ttl` seconds.
+pub fn open_cached(
+    project_management: &ProjectManagement,
+    mode: CacheMode,
+    is_verbose: bool,
+) -> anyhow::Result<Box<dyn IssueTracker>, GinspError> {
+    Ok(Box::new(Cached::new(
+        open(project_management, is_verbose)?,
+        Cached::default_path(project_management),
+        project_management.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL),
+        mode,
+    )))
+}
+
+/// Fetch each ticket with its own request, concurrently.
+fn fetch_each(
+    http: &Http,
+    ids: &[String],
+    fetch: impl Fn(&str) -> Result<Ticket, GinspError> + Sync,
+) -> HashMap<String, Result<Ticket, GinspError>> {
+    let tickets = http.for_each(ids, |id| fetch(id));
+    ids.iter().cloned().zip(tickets).collect()
+}
+
+/// Repository and issue number of a `owner/repo#123` reference,
+/// or of a `#123` reference in the default repository.
+fn parse_issue_ref<'a>(
+    id: &'a str,
+    default_repository: Option<&'a str>,
+) -> Result<(&'a str, &'a str), GinspError> {
+    let (repository, number) = id
+        .split_once('#')
+        .ok_or(GinspError::Cli(format!("Invalid issue reference {}", id)))?;
+    let repository = match repository {
+        "" => default_repository.ok_or(GinspError::Cli(format!(
+            "Set `repository` in [project_management] to fetch {}",
+            id
+        )))?,
+        repository => repository,
+    };
+    Ok((repository, number))
+}
+
+/// Status of a GitHub or GitLab issue with its labels and milestone,
+/// e.g. `closed [bug, ui] (v1.23)`.
+fn issue_status(state: &str, labels: &[&str], milestone: Option<&str>) -> String {
+    let mut status = state.to_string();
+    if !labels.is_empty() {
+        status.push_str(&format!(" [{}]", labels.join(", ")));
+    }
+    if let Some(milestone) = milestone {
+        status.push_str(&format!(" ({})", milestone));
+    }
+    status
+}
+
+/// Split a `<username>:<password>` credential key.
+fn split_credential_key(credential_key: &str) -> anyhow::Result<(&str, &str), GinspError> {
+    credential_key
+        .split_once(':')
+        .ok_or(GinspError::Config(ConfigErrorKind::InvalidCredentialKey))
+}
@@ -0,0 +1,64 @@
+use crate::error::{ConfigErrorKind, GinspError};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_WORKSPACE_FILE: &str = "ginsp-workspace.toml";
+
+/// A workspace file lists the repositories that share release branch names,
+/// so that `update` and `diff-message` can run across all of them.
+///
+/// ```toml
+/// # ginsp-workspace.toml
+/// repos = ["../service-a", "../service-b"]
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct Workspace {
+    pub repos: Vec<PathBuf>,
+
+    /// Directory of the workspace file. Relative repo paths are resolved against it.
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+/// A repository that a command runs against.
+pub struct Repository {
+    /// Name used to group the output of the repository.
+    pub name: String,
+    /// Path passed to git as `-C <path>`, `None` for the current directory.
+    pub path: Option<PathBuf>,
+}
+
+impl Workspace {
+    pub fn read_toml_file(path: &Path) -> anyhow::Result<Workspace, GinspError> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|err| GinspError::Config(ConfigErrorKind::IO(err)))?;
+
+        let mut workspace: Workspace = toml::from_str(toml.as_str())
+            .map_err(|err| GinspError::Config(ConfigErrorKind::Syntax(err)))?;
+
+        // a bare file name has an empty parent, which is the current directory
+        workspace.root = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        Ok(workspace)
+    }
+
+    pub fn repositories(&self) -> Vec<Repository> {
+        self.repos
+            .iter()
+            .map(|repo| {
+                let path = self.root.join(repo);
+                let name = repo
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string_lossy().to_string());
+                Repository {
+                    name,
+                    path: Some(path),
+                }
+            })
+            .collect()
+    }
+}